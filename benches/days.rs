@@ -0,0 +1,106 @@
+//! Benchmarks of every parser, part and variant on the example input of each day and on
+//! a generated input several times larger than the official one, so that slowdowns show
+//! at scale.
+//!
+//! Save a baseline with `cargo bench -- --save-baseline <NAME>` and compare against it
+//! with `cargo bench -- --baseline <NAME>`.
+
+use advent_of_code_2021::inputs;
+use advent_of_code_2021::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+/// Generated input sizes, indexed by day minus one, several times the
+/// [`inputs::DEFAULT_SIZES`] where a part stays under a second or so. Aligning scanners
+/// gets slow quickly, so day 19 uses half of its default size; the sizes of days 21, 23
+/// and 24 are ignored.
+const BENCH_SIZES: [usize; 25] = [
+    100_000, 100_000, 10_000, 1_000, 3_000, 3_000, 10_000, 2_000, 300, 1_000, 30, 12, 8_000, 200,
+    200, 600, 500, 300, 6, 300, 1, 600, 1, 1, 250,
+];
+const SEED: u64 = 2021;
+
+fn bench_input<S: Solver>(c: &mut Criterion, name: &str, input: &str) {
+    let input = input.trim_end_matches('\n');
+    let mut group = c.benchmark_group(format!("day{:02}/{}", S::DAY, name));
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input))));
+
+    let parsed = S::parse(input).expect("benchmark inputs are valid");
+
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+
+    for (variant, part1) in S::part1_variants() {
+        group.bench_function(format!("part1/{}", variant), |b| {
+            b.iter(|| part1(black_box(&parsed)))
+        });
+    }
+
+    if S::PARTS >= 2 {
+        group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+
+        for (variant, part2) in S::part2_variants() {
+            group.bench_function(format!("part2/{}", variant), |b| {
+                b.iter(|| part2(black_box(&parsed)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_day<S: Solver>(c: &mut Criterion, example: Option<&str>) {
+    if let Some(example) = example {
+        bench_input::<S>(c, "example", example);
+    }
+
+    let size = BENCH_SIZES[S::DAY as usize - 1];
+    let generated = inputs::generate(S::DAY, SEED, Some(size)).unwrap();
+
+    bench_input::<S>(c, "generated", &generated);
+}
+
+macro_rules! example {
+    ($file:literal) => {
+        Some(include_str!(concat!("examples/", $file)))
+    };
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c, example!("day01.txt"));
+    bench_day::<day02::Day02>(c, example!("day02.txt"));
+    bench_day::<day03::Day03>(c, example!("day03.txt"));
+    bench_day::<day04::Day04>(c, example!("day04.txt"));
+    bench_day::<day05::Day05>(c, example!("day05.txt"));
+    bench_day::<day06::Day06>(c, example!("day06.txt"));
+    bench_day::<day07::Day07>(c, example!("day07.txt"));
+    bench_day::<day08::Day08>(c, example!("day08.txt"));
+    bench_day::<day09::Day09>(c, example!("day09.txt"));
+    bench_day::<day10::Day10>(c, example!("day10.txt"));
+    bench_day::<day11::Day11>(c, example!("day11.txt"));
+    bench_day::<day12::Day12>(c, example!("day12.txt"));
+    bench_day::<day13::Day13>(c, example!("day13.txt"));
+    bench_day::<day14::Day14>(c, example!("day14.txt"));
+    bench_day::<day15::Day15>(c, example!("day15.txt"));
+    bench_day::<day16::Day16>(c, example!("day16.txt"));
+    bench_day::<day17::Day17>(c, example!("day17.txt"));
+    bench_day::<day18::Day18>(c, example!("day18.txt"));
+    bench_day::<day19::Day19>(c, example!("day19.txt"));
+    bench_day::<day20::Day20>(c, example!("day20.txt"));
+    bench_day::<day21::Day21>(c, example!("day21.txt"));
+    bench_day::<day22::Day22>(c, example!("day22.txt"));
+    bench_day::<day23::Day23>(c, example!("day23.txt"));
+    // The puzzle has no example input.
+    bench_day::<day24::Day24>(c, None);
+    bench_day::<day25::Day25>(c, example!("day25.txt"));
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = days
+}
+criterion_main!(benches);
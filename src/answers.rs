@@ -0,0 +1,215 @@
+use crate::error::RunError;
+use crate::runner::Solution;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Identifies a puzzle input independently of its trailing newlines.
+pub fn input_hash(input: &str) -> u64 {
+    input
+        .trim_end_matches('\n')
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
+/// Known-correct answers, keyed by day, part and input hash.
+///
+/// Stored as text with one answer per line: `<day> <part> <hash> <answer>`, where the hash
+/// is written in hexadecimal and newlines and backslashes in the answer are escaped.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerStore(BTreeMap<(u8, u8, u64), String>);
+
+impl AnswerStore {
+    pub fn get(&self, day: u8, part: u8, input_hash: u64) -> Option<&str> {
+        self.0
+            .get(&(day, part, input_hash))
+            .map(|answer| answer.as_str())
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, input_hash: u64, answer: String) {
+        self.0.insert((day, part, input_hash), answer);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnswerStoreError {
+    line: usize,
+    message: &'static str,
+}
+
+impl Display for AnswerStoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "answers, line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AnswerStoreError {}
+
+impl TryFrom<&str> for AnswerStore {
+    type Error = AnswerStoreError;
+
+    fn try_from(store: &str) -> Result<Self, Self::Error> {
+        let mut answers = BTreeMap::new();
+
+        for (index, line) in store.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message| AnswerStoreError {
+                line: index + 1,
+                message,
+            };
+            let mut split = line.splitn(4, ' ');
+            let mut next_field = |message| split.next().ok_or_else(|| error(message));
+
+            let day = next_field("missing day")?
+                .parse()
+                .map_err(|_| error("invalid day"))?;
+            let part = next_field("missing part")?
+                .parse()
+                .map_err(|_| error("invalid part"))?;
+            let hash = u64::from_str_radix(next_field("missing input hash")?, 16)
+                .map_err(|_| error("invalid input hash"))?;
+            let answer =
+                unescape(next_field("missing answer")?).ok_or_else(|| error("invalid escape"))?;
+
+            answers.insert((day, part, hash), answer);
+        }
+
+        Ok(AnswerStore(answers))
+    }
+}
+
+impl Display for AnswerStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# day part input-hash answer")?;
+
+        for ((day, part, hash), answer) in self.0.iter() {
+            writeln!(f, "{} {} {:016x} {}", day, part, hash, escape(answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> Option<String> {
+    let mut result = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => result.push('\n'),
+                '\\' => result.push('\\'),
+                _ => return None,
+            },
+            c => result.push(c),
+        }
+    }
+
+    Some(result)
+}
+
+/// Outcome of running a solution against an input with a stored answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch { expected: String, actual: String },
+    Missing,
+    Failed(RunError),
+}
+
+pub fn check(store: &AnswerStore, solution: &Solution, input: &str) -> Check {
+    let expected = match store.get(solution.day, solution.part, input_hash(input)) {
+        Some(expected) => expected,
+        None => return Check::Missing,
+    };
+
+    match solution.run(input) {
+        Ok(output) if output.answer == expected => Check::Match,
+        Ok(output) => Check::Mismatch {
+            expected: expected.to_owned(),
+            actual: output.answer,
+        },
+        Err(error) => Check::Failed(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{find, solutions};
+    use std::fs;
+
+    static TEST_INPUT: &str = "16,1,2,0,4,2,7,1,2,14\n";
+
+    #[test]
+    fn store_round_trip() {
+        let mut store = AnswerStore::default();
+        store.insert(13, 2, input_hash(TEST_INPUT), "\n#..\\\n.#.".to_owned());
+        store.insert(7, 1, 1, "37".to_owned());
+
+        assert_eq!(AnswerStore::try_from(store.to_string().as_str()), Ok(store));
+        assert!(AnswerStore::try_from("7 1 zz 37").is_err());
+    }
+
+    #[test]
+    fn check_variants() {
+        let mut store = AnswerStore::default();
+        store.insert(7, 1, input_hash(TEST_INPUT), "37".to_owned());
+        store.insert(7, 2, input_hash(TEST_INPUT), "170".to_owned());
+
+        let median = find(7, 1, Some("median")).unwrap();
+        let mean = find(7, 2, Some("mean")).unwrap();
+
+        assert_eq!(check(&store, &median, TEST_INPUT), Check::Match);
+        assert_eq!(
+            check(&store, &mean, TEST_INPUT),
+            Check::Mismatch {
+                expected: "170".to_owned(),
+                actual: "168".to_owned()
+            }
+        );
+        assert_eq!(check(&store, &median, "1,2,3"), Check::Missing);
+    }
+
+    /// Every part and variant still gives the checked-in answers on the example inputs.
+    #[test]
+    fn stored_example_answers() {
+        let store = AnswerStore::try_from(include_str!("../answers.txt")).unwrap();
+        let examples = concat!(env!("CARGO_MANIFEST_DIR"), "/benches/examples");
+
+        // Day 24 has no example input.
+        for day in (1..=25).filter(|day| *day != 24) {
+            let input = fs::read_to_string(format!("{}/day{:02}.txt", examples, day)).unwrap();
+
+            for solution in solutions().filter(|s| s.day == day) {
+                assert_eq!(
+                    check(&store, &solution, &input),
+                    Check::Match,
+                    "{}",
+                    solution
+                );
+            }
+        }
+    }
+}
@@ -1,19 +1,271 @@
+use crate::error::{parse_number, ParseError};
+use crate::json::Json;
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
-use std::num::ParseIntError;
+use std::cmp::Ordering::*;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, ErrorKind};
+
+const DAY: u8 = 1;
 
 #[aoc_generator(day1)]
-fn parse_input(input: &str) -> Result<Vec<u32>, ParseIntError> {
-    input.lines().map(|l| l.parse()).collect()
+fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    let input: &str = &normalize(input);
+
+    input.lines().map(|l| parse_number(DAY, input, l)).collect()
+}
+
+/// How the sums of consecutive sliding windows compare.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SweepCounts {
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+}
+
+/// Compares the sums of sliding windows of depths as they arrive, keeping only the last
+/// `window` depths: two consecutive windows share all but their first and last depth,
+/// so their sums compare like those two depths.
+#[derive(Clone, Debug)]
+pub struct SonarAnalyzer {
+    window: usize,
+    recent: VecDeque<u32>,
+    counts: SweepCounts,
+}
+
+impl SonarAnalyzer {
+    /// The window of part 1, which compares single depths.
+    pub const DEPTHS: usize = 1;
+    /// The window of part 2.
+    pub const SLIDING_WINDOWS: usize = 3;
+
+    /// Panics if `window` is zero.
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "the window must hold at least one depth");
+
+        Self {
+            window,
+            recent: VecDeque::with_capacity(window + 1),
+            counts: SweepCounts::default(),
+        }
+    }
+
+    pub fn push(&mut self, depth: u32) {
+        self.recent.push_back(depth);
+
+        if self.recent.len() <= self.window {
+            return;
+        }
+
+        let first = self.recent.pop_front().unwrap();
+
+        match depth.cmp(&first) {
+            Greater => self.counts.increases += 1,
+            Less => self.counts.decreases += 1,
+            Equal => self.counts.plateaus += 1,
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn counts(&self) -> SweepCounts {
+        self.counts
+    }
+}
+
+/// Compares the sums of consecutive windows of `window` depths.
+pub fn analyze(depths: impl IntoIterator<Item = u32>, window: usize) -> SweepCounts {
+    let mut analyzer = SonarAnalyzer::new(window);
+    depths.into_iter().for_each(|depth| analyzer.push(depth));
+    analyzer.counts()
+}
+
+/// Like [`analyze`], reading one depth per line from `reader` without buffering the
+/// whole input. Blank lines are skipped; an invalid depth is reported as an
+/// [`ErrorKind::InvalidData`] error wrapping a [`ParseError`].
+pub fn analyze_reader(mut reader: impl BufRead, window: usize) -> io::Result<SweepCounts> {
+    let mut analyzer = SonarAnalyzer::new(window);
+    let mut line = String::new();
+    let mut number = 0;
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(analyzer.counts());
+        }
+
+        number += 1;
+
+        let depth = line.trim_start_matches('\u{feff}').trim();
+
+        if depth.is_empty() {
+            continue;
+        }
+
+        let depth = parse_number(DAY, depth, depth)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e.on_line(number)))?;
+
+        analyzer.push(depth);
+    }
+}
+
+/// Consecutive windows whose sums keep increasing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Run {
+    /// Index of the first window of the run.
+    pub start: usize,
+    pub increases: usize,
+}
+
+/// The change in sum from one window to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Jump {
+    /// Index of the later window.
+    pub index: usize,
+    pub change: i64,
+}
+
+/// Statistics of the sums of the sliding windows of `window` depths. Window `i` covers
+/// depths `i..i + window`, and step `i` goes from window `i - 1` to window `i`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SonarReport {
+    pub window: usize,
+    /// The first of the longest runs, if the sum ever increases.
+    pub longest_increasing_run: Option<Run>,
+    /// The first of the largest changes in either direction, if there are two windows.
+    pub largest_jump: Option<Jump>,
+    pub moving_averages: Vec<f64>,
+    pub decreasing_steps: Vec<usize>,
+}
+
+impl SonarReport {
+    /// Panics if `window` is zero.
+    pub fn new(depths: &[u32], window: usize) -> Self {
+        assert!(window > 0, "the window must hold at least one depth");
+
+        let sums = depths
+            .windows(window)
+            .map(|w| w.iter().map(|&depth| depth as u64).sum::<u64>())
+            .collect::<Vec<_>>();
+
+        let mut longest_increasing_run: Option<Run> = None;
+        let mut largest_jump: Option<Jump> = None;
+        let mut decreasing_steps = Vec::new();
+        let mut run = Run {
+            start: 0,
+            increases: 0,
+        };
+
+        for (index, pair) in sums.windows(2).enumerate().map(|(i, pair)| (i + 1, pair)) {
+            let change = pair[1] as i64 - pair[0] as i64;
+
+            if largest_jump.is_none_or(|jump| change.abs() > jump.change.abs()) {
+                largest_jump = Some(Jump { index, change });
+            }
+
+            if change > 0 {
+                run.increases += 1;
+
+                if longest_increasing_run.is_none_or(|longest| run.increases > longest.increases) {
+                    longest_increasing_run = Some(run);
+                }
+            } else {
+                run = Run {
+                    start: index,
+                    increases: 0,
+                };
+            }
+
+            if change < 0 {
+                decreasing_steps.push(index);
+            }
+        }
+
+        Self {
+            window,
+            longest_increasing_run,
+            largest_jump,
+            moving_averages: sums.iter().map(|&sum| sum as f64 / window as f64).collect(),
+            decreasing_steps,
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("window", self.window.into()),
+            (
+                "longest_increasing_run",
+                self.longest_increasing_run
+                    .map(|run| {
+                        Json::object([
+                            ("start", run.start.into()),
+                            ("increases", run.increases.into()),
+                        ])
+                    })
+                    .into(),
+            ),
+            (
+                "largest_jump",
+                self.largest_jump
+                    .map(|jump| {
+                        Json::object([("index", jump.index.into()), ("change", jump.change.into())])
+                    })
+                    .into(),
+            ),
+            ("moving_averages", self.moving_averages.clone().into()),
+            ("decreasing_steps", self.decreasing_steps.clone().into()),
+        ])
+    }
 }
 
 #[aoc(day1, part1)]
 fn part1(entries: &[u32]) -> usize {
-    entries.windows(2).filter(|&x| x[1] > x[0]).count()
+    analyze(entries.iter().copied(), SonarAnalyzer::DEPTHS).increases
 }
 
 #[aoc(day1, part2)]
 fn part2(entries: &[u32]) -> usize {
-    entries.windows(4).filter(|&x| x[3] > x[0]).count()
+    analyze(entries.iter().copied(), SonarAnalyzer::SLIDING_WINDOWS).increases
+}
+
+/// Day 1: Sonar Sweep.
+pub struct Day01;
+
+impl Solver for Day01 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+
+    fn details(input: &Self::Input) -> Vec<(&'static str, Json)> {
+        vec![
+            (
+                "depths",
+                SonarReport::new(input, SonarAnalyzer::DEPTHS).to_json(),
+            ),
+            (
+                "sliding_windows",
+                SonarReport::new(input, SonarAnalyzer::SLIDING_WINDOWS).to_json(),
+            ),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -35,4 +287,84 @@ mod tests {
             5
         );
     }
+
+    static TEST_INPUT: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn window_sizes() {
+        let counts = |increases, decreases, plateaus| SweepCounts {
+            increases,
+            decreases,
+            plateaus,
+        };
+
+        assert_eq!(analyze(TEST_INPUT, 1), counts(7, 2, 0));
+        assert_eq!(analyze(TEST_INPUT, 3), counts(5, 1, 1));
+        assert_eq!(analyze(TEST_INPUT, 9), counts(1, 0, 0));
+        assert_eq!(analyze(TEST_INPUT, 10), counts(0, 0, 0));
+    }
+
+    #[test]
+    fn streamed_input() {
+        let input =
+            "\u{feff}199\r\n200\r\n208\r\n210\r\n200\r\n207\r\n240\r\n269\r\n260\r\n263\r\n\r\n";
+        assert_eq!(
+            analyze_reader(input.as_bytes(), 3).unwrap(),
+            analyze(TEST_INPUT, 3)
+        );
+
+        let error = analyze_reader("1\n2\nthree\n4".as_bytes(), 1).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 3, column 1: invalid number `three`"
+        );
+    }
+
+    #[test]
+    fn report_example() {
+        let depths = SonarReport::new(&TEST_INPUT, 1);
+
+        assert_eq!(
+            depths.longest_increasing_run,
+            Some(Run {
+                start: 0,
+                increases: 3
+            })
+        );
+        assert_eq!(
+            depths.largest_jump,
+            Some(Jump {
+                index: 6,
+                change: 33
+            })
+        );
+        assert_eq!(depths.decreasing_steps, vec![4, 8]);
+
+        let windows = SonarReport::new(&TEST_INPUT, 3);
+
+        assert_eq!(
+            windows.longest_increasing_run,
+            Some(Run {
+                start: 3,
+                increases: 4
+            })
+        );
+        assert_eq!(
+            windows.largest_jump,
+            Some(Jump {
+                index: 5,
+                change: 69
+            })
+        );
+        assert_eq!(windows.decreasing_steps, vec![3]);
+        assert_eq!(windows.moving_averages[4], 647.0 / 3.0);
+        assert_eq!(windows.moving_averages.len(), 8);
+
+        let flat = SonarReport::new(&[5, 5], 2);
+
+        assert_eq!(flat.longest_increasing_run, None);
+        assert_eq!(flat.largest_jump, None);
+        assert_eq!(flat.moving_averages, vec![5.0]);
+    }
 }
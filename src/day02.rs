@@ -1,96 +1,719 @@
+use crate::error::{next_part, parse_number, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+use std::iter::Peekable;
 
-struct Coordinates {
-    horizontal_position: u32,
-    depth: u32,
-}
-
-struct AimedCoordinates {
-    horizontal_position: u32,
-    depth: u32,
-    aim: u32,
-}
+const DAY: u8 = 2;
 
-enum Direction {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
     Forward,
     Down,
     Up,
+    Back,
 }
 
-impl From<&str> for Direction {
-    fn from(direction_str: &str) -> Self {
+impl TryFrom<&str> for Direction {
+    type Error = ParseError;
+
+    fn try_from(direction_str: &str) -> Result<Self, Self::Error> {
         match direction_str {
-            "forward" => Self::Forward,
-            "down" => Self::Down,
-            "up" => Self::Up,
-            _ => unreachable!(),
+            "forward" => Ok(Self::Forward),
+            "down" => Ok(Self::Down),
+            "up" => Ok(Self::Up),
+            "back" => Ok(Self::Back),
+            _ => Err(ParseError::new(
+                DAY,
+                direction_str,
+                direction_str,
+                "unknown direction",
+            )),
         }
     }
 }
 
-struct Command {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Command {
     direction: Direction,
     units: u32,
 }
 
-impl From<&str> for Command {
-    fn from(command_str: &str) -> Self {
-        let mut iter = command_str.split_whitespace();
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Forward => write!(f, "forward"),
+            Self::Down => write!(f, "down"),
+            Self::Up => write!(f, "up"),
+            Self::Back => write!(f, "back"),
+        }
+    }
+}
 
-        let direction: Direction = iter.next().unwrap().into();
-        let units = iter.next().unwrap().parse::<u32>().unwrap();
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.direction, self.units)
+    }
+}
 
+impl Command {
+    pub fn new(direction: Direction, units: u32) -> Self {
         Self { direction, units }
     }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn units(&self) -> u32 {
+        self.units
+    }
+}
+
+impl TryFrom<&str> for Command {
+    type Error = ParseError;
+
+    fn try_from(command_str: &str) -> Result<Self, Self::Error> {
+        let mut iter = command_str.split_whitespace();
+
+        let direction_str = next_part(DAY, command_str, &mut iter, "direction")?;
+        let direction =
+            Direction::try_from(direction_str).map_err(|e| e.within(command_str, direction_str))?;
+        let units = parse_number(
+            DAY,
+            command_str,
+            next_part(DAY, command_str, &mut iter, "units")?,
+        )?;
+
+        if let Some(extra) = iter.next() {
+            return Err(ParseError::new(DAY, command_str, extra, "unexpected text"));
+        }
+
+        Ok(Self { direction, units })
+    }
+}
+
+/// Most commands that the `repeat` blocks and macro uses of a script may expand to;
+/// commands written out in the script do not count.
+const MAX_COMMANDS: usize = 10_000_000;
+
+/// Splits a script into whitespace-separated tokens with their line index, dropping
+/// comments from `#` to the end of the line. Braces are tokens of their own.
+fn tokens(script: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+
+    for (line_index, line) in script.lines().enumerate() {
+        let code = line.split('#').next().unwrap_or_default();
+        let mut start = None;
+
+        for (i, c) in code.char_indices() {
+            if c.is_whitespace() || c == '{' || c == '}' {
+                if let Some(start) = start.take() {
+                    tokens.push((line_index, &code[start..i]));
+                }
+
+                if c != '{' && c != '}' {
+                    continue;
+                }
+
+                tokens.push((line_index, &code[i..i + 1]));
+            } else if start.is_none() {
+                start = Some(i);
+            }
+        }
+
+        if let Some(start) = start {
+            tokens.push((line_index, &code[start..]));
+        }
+    }
+
+    tokens
+}
+
+/// Compiles a command script to the commands it stands for. Besides one command per
+/// line, a script may contain comments, blank lines, `repeat N { ... }` blocks and
+/// macros defined with `macro NAME { ... }` and used by name after their definition.
+/// Only closing braces may follow a command on its line.
+struct Compiler<'a> {
+    script: &'a str,
+    tokens: Peekable<std::vec::IntoIter<(usize, &'a str)>>,
+    macros: HashMap<&'a str, Vec<Command>>,
+    /// Commands produced by `repeat` blocks and macro uses so far.
+    expanded: usize,
+}
+
+impl<'a> Compiler<'a> {
+    fn new(script: &'a str) -> Self {
+        Self {
+            script,
+            tokens: tokens(script).into_iter().peekable(),
+            macros: HashMap::new(),
+            expanded: 0,
+        }
+    }
+
+    fn error(&self, token: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(DAY, self.script, token, message)
+    }
+
+    fn next(&mut self, after: &str, what: &str) -> Result<&'a str, ParseError> {
+        self.tokens
+            .next()
+            .map(|(_, token)| token)
+            .ok_or_else(|| self.error(after, format!("missing {} after `{}`", what, after)))
+    }
+
+    /// Counts `len` more expanded commands, failing past [`MAX_COMMANDS`].
+    fn expand(&mut self, token: &str, len: Option<usize>) -> Result<(), ParseError> {
+        match len.and_then(|len| len.checked_add(self.expanded)) {
+            Some(expanded) if expanded <= MAX_COMMANDS => {
+                self.expanded = expanded;
+                Ok(())
+            }
+            _ => Err(self.error(token, "the script expands to too many commands")),
+        }
+    }
+
+    /// The command starting with `token`, which must be alone on `line`.
+    fn command(&mut self, line: usize, token: &str) -> Result<Command, ParseError> {
+        let direction =
+            Direction::try_from(token).map_err(|_| self.error(token, "unknown command"))?;
+        let units = match self.tokens.next_if(|(l, _)| *l == line) {
+            Some((_, units)) => parse_number(DAY, self.script, units)?,
+            None => return Err(self.error(token, format!("missing units after `{}`", token))),
+        };
+        let extra = self
+            .tokens
+            .peek()
+            .filter(|(l, t)| *l == line && *t != "}")
+            .map(|(_, t)| *t);
+
+        if let Some(extra) = extra {
+            return Err(self.error(extra, "unexpected text"));
+        }
+
+        Ok(Command { direction, units })
+    }
+
+    fn open(&mut self, after: &str) -> Result<&'a str, ParseError> {
+        let token = self.next(after, "`{`")?;
+
+        match token {
+            "{" => Ok(token),
+            _ => Err(self.error(token, "expected `{`")),
+        }
+    }
+
+    /// Compiles statements up to the `}` closing `opening`, or to the end of the script.
+    fn block(&mut self, opening: Option<&str>) -> Result<Vec<Command>, ParseError> {
+        let mut commands = Vec::new();
+
+        while let Some((line, token)) = self.tokens.next() {
+            match token {
+                "}" if opening.is_some() => return Ok(commands),
+                "}" => return Err(self.error(token, "unmatched `}`")),
+                "repeat" => {
+                    let count: usize =
+                        parse_number(DAY, self.script, self.next(token, "repeat count")?)?;
+                    let opening = self.open(token)?;
+                    let body = self.block(Some(opening))?;
+
+                    self.expand(token, body.len().checked_mul(count))?;
+
+                    if !body.is_empty() {
+                        for _ in 0..count {
+                            commands.extend_from_slice(&body);
+                        }
+                    }
+                }
+                "macro" => {
+                    let name = self.next(token, "macro name")?;
+
+                    if name == "{" || name == "}" || Direction::try_from(name).is_ok() {
+                        return Err(self.error(name, "invalid macro name"));
+                    }
+
+                    if ["repeat", "macro"].contains(&name) {
+                        return Err(self.error(name, "reserved macro name"));
+                    }
+
+                    if self.macros.contains_key(name) {
+                        return Err(self.error(name, "macro already defined"));
+                    }
+
+                    let opening = self.open(name)?;
+                    let body = self.block(Some(opening))?;
+                    self.macros.insert(name, body);
+                }
+                _ => {
+                    if let Some(len) = self.macros.get(token).map(Vec::len) {
+                        self.expand(token, Some(len))?;
+                        commands.extend_from_slice(&self.macros[token]);
+                        continue;
+                    }
+
+                    commands.push(self.command(line, token)?);
+                }
+            }
+        }
+
+        match opening {
+            Some(opening) => Err(self.error(opening, "unclosed `{`")),
+            None => Ok(commands),
+        }
+    }
 }
 
 #[aoc_generator(day2)]
-fn parse_input(input: &str) -> Vec<Command> {
-    input.lines().map(|l| l.into()).collect()
+fn parse_input(input: &str) -> Result<Vec<Command>, ParseError> {
+    let input: &str = &normalize(input);
+
+    Compiler::new(input).block(None)
 }
 
-#[aoc(day2, part1)]
-fn part1(commands: &[Command]) -> u32 {
-    use Direction::*;
+/// A state the submarine cannot be in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidState {
+    AboveSurface,
+    Overflow,
+}
+
+impl Display for InvalidState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidState::AboveSurface => write!(f, "takes the submarine above the surface"),
+            InvalidState::Overflow => write!(f, "overflows the submarine state"),
+        }
+    }
+}
+
+/// What to do when a command would take the submarine above the surface.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SurfacePolicy {
+    #[default]
+    Error,
+    /// Stop at the surface instead.
+    Clamp,
+}
+
+impl SurfacePolicy {
+    fn surface(self, depth: i64) -> Result<i64, InvalidState> {
+        match (depth < 0, self) {
+            (false, _) => Ok(depth),
+            (true, SurfacePolicy::Clamp) => Ok(0),
+            (true, SurfacePolicy::Error) => Err(InvalidState::AboveSurface),
+        }
+    }
+}
+
+fn add(a: i64, b: i64) -> Result<i64, InvalidState> {
+    a.checked_add(b).ok_or(InvalidState::Overflow)
+}
+
+fn mul(a: i64, b: i64) -> Result<i64, InvalidState> {
+    a.checked_mul(b).ok_or(InvalidState::Overflow)
+}
+
+/// How a submarine moves in response to each command.
+pub trait SubmarineModel {
+    /// Fails without moving if the command would leave the submarine in an invalid
+    /// state.
+    fn apply(&mut self, command: &Command) -> Result<(), InvalidState>;
+
+    fn horizontal_position(&self) -> i64;
 
-    let mut coordinates = Coordinates {
-        horizontal_position: 0,
-        depth: 0,
-    };
+    fn depth(&self) -> i64;
+
+    /// The aim, in models that have one.
+    fn aim(&self) -> Option<i64> {
+        None
+    }
+}
+
+/// `down` and `up` change the depth directly.
+#[derive(Clone, Debug, Default)]
+pub struct Coordinates {
+    horizontal_position: i64,
+    depth: i64,
+    policy: SurfacePolicy,
+}
+
+impl Coordinates {
+    pub fn new(policy: SurfacePolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+}
+
+impl SubmarineModel for Coordinates {
+    fn apply(&mut self, command: &Command) -> Result<(), InvalidState> {
+        use Direction::*;
+
+        let units = command.units as i64;
 
-    for command in commands {
         match command.direction {
-            Forward => coordinates.horizontal_position += command.units,
-            Down => coordinates.depth += command.units,
-            Up => coordinates.depth -= command.units,
+            Forward => self.horizontal_position = add(self.horizontal_position, units)?,
+            Back => self.horizontal_position = add(self.horizontal_position, -units)?,
+            Down => self.depth = self.policy.surface(add(self.depth, units)?)?,
+            Up => self.depth = self.policy.surface(add(self.depth, -units)?)?,
         }
+
+        Ok(())
+    }
+
+    fn horizontal_position(&self) -> i64 {
+        self.horizontal_position
+    }
+
+    fn depth(&self) -> i64 {
+        self.depth
     }
+}
 
-    coordinates.horizontal_position * coordinates.depth
+/// `down` and `up` change the aim, and moving forward or back changes the depth by the
+/// aim.
+#[derive(Clone, Debug, Default)]
+pub struct AimedCoordinates {
+    horizontal_position: i64,
+    depth: i64,
+    aim: i64,
+    policy: SurfacePolicy,
 }
 
-#[aoc(day2, part2)]
-fn part2(commands: &[Command]) -> u32 {
-    use Direction::*;
+impl AimedCoordinates {
+    pub fn new(policy: SurfacePolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+}
 
-    let mut coordinates = AimedCoordinates {
-        horizontal_position: 0,
-        depth: 0,
-        aim: 0,
-    };
+impl SubmarineModel for AimedCoordinates {
+    fn apply(&mut self, command: &Command) -> Result<(), InvalidState> {
+        use Direction::*;
+
+        let units = command.units as i64;
 
-    for command in commands {
         match command.direction {
-            Forward => {
-                coordinates.horizontal_position += command.units;
-                coordinates.depth += coordinates.aim * command.units;
+            Forward | Back => {
+                let units = if command.direction == Back {
+                    -units
+                } else {
+                    units
+                };
+                let horizontal_position = add(self.horizontal_position, units)?;
+                let depth = add(self.depth, mul(self.aim, units)?)?;
+
+                self.depth = self.policy.surface(depth)?;
+                self.horizontal_position = horizontal_position;
             }
-            Down => coordinates.aim += command.units,
-            Up => coordinates.aim -= command.units,
+            Down => self.aim = add(self.aim, units)?,
+            Up => self.aim = add(self.aim, -units)?,
         }
+
+        Ok(())
+    }
+
+    fn horizontal_position(&self) -> i64 {
+        self.horizontal_position
     }
 
-    coordinates.horizontal_position * coordinates.depth
+    fn depth(&self) -> i64 {
+        self.depth
+    }
+
+    fn aim(&self) -> Option<i64> {
+        Some(self.aim)
+    }
+}
+
+/// Aims like [`AimedCoordinates`], but `forward` and `back` change the speed instead of
+/// moving directly. Every command takes one unit of time, during which the submarine keeps
+/// moving forward by its speed and down by the aim times its speed.
+#[derive(Clone, Debug, Default)]
+pub struct InertialCoordinates {
+    horizontal_position: i64,
+    depth: i64,
+    aim: i64,
+    speed: i64,
+    policy: SurfacePolicy,
+}
+
+impl InertialCoordinates {
+    pub fn new(policy: SurfacePolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+}
+
+impl SubmarineModel for InertialCoordinates {
+    fn apply(&mut self, command: &Command) -> Result<(), InvalidState> {
+        use Direction::*;
+
+        let (mut aim, mut speed) = (self.aim, self.speed);
+        let units = command.units as i64;
+
+        match command.direction {
+            Forward => speed = add(speed, units)?,
+            Back => speed = add(speed, -units)?,
+            Down => aim = add(aim, units)?,
+            Up => aim = add(aim, -units)?,
+        }
+
+        let horizontal_position = add(self.horizontal_position, speed)?;
+        let depth = self.policy.surface(add(self.depth, mul(aim, speed)?)?)?;
+
+        *self = Self {
+            horizontal_position,
+            depth,
+            aim,
+            speed,
+            policy: self.policy,
+        };
+
+        Ok(())
+    }
+
+    fn horizontal_position(&self) -> i64 {
+        self.horizontal_position
+    }
+
+    fn depth(&self) -> i64 {
+        self.depth
+    }
+
+    fn aim(&self) -> Option<i64> {
+        Some(self.aim)
+    }
+}
+
+/// Names of the models accepted by [`model`].
+pub const MODELS: [&str; 3] = ["simple", "aimed", "inertial"];
+
+/// A submarine at the surface following the model called `name`.
+pub fn model(name: &str, policy: SurfacePolicy) -> Option<Box<dyn SubmarineModel>> {
+    match name {
+        "simple" => Some(Box::new(Coordinates::new(policy))),
+        "aimed" => Some(Box::new(AimedCoordinates::new(policy))),
+        "inertial" => Some(Box::new(InertialCoordinates::new(policy))),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavigationError {
+    /// The command at `index` would leave the submarine in an invalid state.
+    InvalidCommand {
+        index: usize,
+        command: Command,
+        state: InvalidState,
+    },
+    /// The horizontal position times the depth does not fit.
+    ProductOverflow,
+}
+
+impl Display for NavigationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavigationError::InvalidCommand {
+                index,
+                command,
+                state,
+            } => write!(f, "command {} `{}` {}", index, command, state),
+            NavigationError::ProductOverflow => {
+                write!(f, "the horizontal position times the depth overflows")
+            }
+        }
+    }
+}
+
+impl Error for NavigationError {}
+
+/// Applies every command in order, calling `visit` after each of them.
+fn follow<M: SubmarineModel + ?Sized>(
+    model: &mut M,
+    commands: &[Command],
+    mut visit: impl FnMut(&Command, &M),
+) -> Result<(), NavigationError> {
+    for (index, command) in commands.iter().enumerate() {
+        model
+            .apply(command)
+            .map_err(|state| NavigationError::InvalidCommand {
+                index,
+                command: *command,
+                state,
+            })?;
+
+        visit(command, model);
+    }
+
+    Ok(())
+}
+
+/// Applies every command in order and returns the horizontal position times the depth.
+pub fn navigate(
+    model: &mut (impl SubmarineModel + ?Sized),
+    commands: &[Command],
+) -> Result<i64, NavigationError> {
+    follow(model, commands, |_, _| ())?;
+
+    model
+        .horizontal_position()
+        .checked_mul(model.depth())
+        .ok_or(NavigationError::ProductOverflow)
+}
+
+/// The state of the submarine after a command, or at the start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Waypoint {
+    pub command: Option<Command>,
+    pub horizontal_position: i64,
+    pub depth: i64,
+    pub aim: Option<i64>,
+}
+
+impl Waypoint {
+    fn new(command: Option<Command>, model: &(impl SubmarineModel + ?Sized)) -> Self {
+        Self {
+            command,
+            horizontal_position: model.horizontal_position(),
+            depth: model.depth(),
+            aim: model.aim(),
+        }
+    }
+}
+
+/// Every state of the submarine along its route, starting with the one before the
+/// first command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trajectory(Vec<Waypoint>);
+
+const SVG_WIDTH: i64 = 800;
+const SVG_HEIGHT: i64 = 400;
+
+impl Trajectory {
+    pub fn waypoints(&self) -> &[Waypoint] {
+        &self.0
+    }
+
+    /// Writes one row per waypoint, with an empty aim in models without one.
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "step,command,horizontal_position,depth,aim")?;
+
+        for (step, waypoint) in self.0.iter().enumerate() {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                step,
+                waypoint.command.map(|c| c.to_string()).unwrap_or_default(),
+                waypoint.horizontal_position,
+                waypoint.depth,
+                waypoint.aim.map(|aim| aim.to_string()).unwrap_or_default()
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the depth along the horizontal position as an SVG line chart, with the
+    /// surface at the top and the deepest point at the bottom.
+    pub fn write_svg(&self, out: &mut impl Write) -> io::Result<()> {
+        let horizontal = self.0.iter().map(|w| w.horizontal_position);
+        let (left, right) = (horizontal.clone().min(), horizontal.max());
+        let (left, right) = (left.unwrap_or(0), right.unwrap_or(0));
+        let bottom = self.0.iter().map(|w| w.depth).max().unwrap_or(0);
+
+        let x = |position: i64| {
+            (position - left) as f64 * SVG_WIDTH as f64 / (right - left).max(1) as f64
+        };
+        let y = |depth: i64| depth as f64 * SVG_HEIGHT as f64 / bottom.max(1) as f64;
+
+        let points = self
+            .0
+            .iter()
+            .map(|w| format!("{:.1},{:.1}", x(w.horizontal_position), y(w.depth)))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = SVG_WIDTH,
+            h = SVG_HEIGHT
+        )?;
+        writeln!(
+            out,
+            r##"<rect width="{}" height="{}" fill="#0b3d91"/>"##,
+            SVG_WIDTH, SVG_HEIGHT
+        )?;
+        writeln!(
+            out,
+            r##"<polyline fill="none" stroke="#ffd700" stroke-width="2" points="{}"/>"##,
+            points
+        )?;
+        writeln!(
+            out,
+            "<title>horizontal position {} to {}, depth 0 to {}</title>",
+            left, right, bottom
+        )?;
+        writeln!(out, "</svg>")
+    }
+}
+
+/// Applies every command in order, recording the state of the submarine after each.
+pub fn record(
+    model: &mut (impl SubmarineModel + ?Sized),
+    commands: &[Command],
+) -> Result<Trajectory, NavigationError> {
+    let mut waypoints = vec![Waypoint::new(None, model)];
+
+    follow(model, commands, |command, model| {
+        waypoints.push(Waypoint::new(Some(*command), model))
+    })?;
+
+    Ok(Trajectory(waypoints))
+}
+
+#[aoc(day2, part1)]
+fn part1(commands: &[Command]) -> Option<i64> {
+    navigate(&mut Coordinates::default(), commands).ok()
+}
+
+#[aoc(day2, part2)]
+fn part2(commands: &[Command]) -> Option<i64> {
+    navigate(&mut AimedCoordinates::default(), commands).ok()
+}
+
+/// Day 2: Dive!
+pub struct Day02;
+
+impl Solver for Day02 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Command>;
+    type Part1 = Option<i64>;
+    type Part2 = Option<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -106,11 +729,208 @@ forward 2";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 150);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), Some(150));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 900);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), Some(900));
+    }
+
+    #[test]
+    fn models_by_name() {
+        let commands = parse_input(TEST_INPUT).unwrap();
+        let product = |name| {
+            navigate(
+                model(name, SurfacePolicy::Error).unwrap().as_mut(),
+                &commands,
+            )
+            .ok()
+        };
+
+        assert_eq!(product("simple"), part1(&commands));
+        assert_eq!(product("aimed"), part2(&commands));
+        assert_eq!(product("inertial"), Some(64 * 396));
+        assert!(model("sideways", SurfacePolicy::Error).is_none());
+    }
+
+    #[test]
+    fn surface_policies() {
+        let commands = parse_input("forward 2\ndown 3\nup 5\ndown 1").unwrap();
+
+        let error = navigate(&mut Coordinates::new(SurfacePolicy::Error), &commands);
+        assert_eq!(
+            error,
+            Err(NavigationError::InvalidCommand {
+                index: 2,
+                command: Command::new(Direction::Up, 5),
+                state: InvalidState::AboveSurface,
+            })
+        );
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "command 2 `up 5` takes the submarine above the surface"
+        );
+
+        let mut clamped = Coordinates::new(SurfacePolicy::Clamp);
+        assert_eq!(navigate(&mut clamped, &commands), Ok(2));
+        assert_eq!(clamped.depth(), 1);
+
+        let commands = parse_input("up 5\nforward 2").unwrap();
+        let mut aimed = AimedCoordinates::new(SurfacePolicy::Clamp);
+        assert_eq!(navigate(&mut aimed, &commands), Ok(0));
+        assert_eq!(aimed.aim, -5);
+        assert_eq!(part2(&commands), None);
+    }
+
+    #[test]
+    fn overflow() {
+        let commands = parse_input("down 4294967295\nforward 4294967295").unwrap();
+        assert!(matches!(
+            navigate(&mut AimedCoordinates::default(), &commands),
+            Err(NavigationError::InvalidCommand {
+                index: 1,
+                state: InvalidState::Overflow,
+                ..
+            })
+        ));
+
+        let commands = parse_input("forward 4294967295\n".repeat(3).as_str()).unwrap();
+        let mut deep = Coordinates {
+            depth: 4_294_967_295,
+            ..Coordinates::default()
+        };
+        assert_eq!(
+            navigate(&mut deep, &commands),
+            Err(NavigationError::ProductOverflow)
+        );
+    }
+
+    #[test]
+    fn trajectory() {
+        let commands = parse_input(TEST_INPUT).unwrap();
+        let trajectory = record(&mut AimedCoordinates::default(), &commands).unwrap();
+
+        assert_eq!(trajectory.waypoints().len(), 7);
+        assert_eq!(
+            trajectory.waypoints()[6],
+            Waypoint {
+                command: Some(Command::new(Direction::Forward, 2)),
+                horizontal_position: 15,
+                depth: 60,
+                aim: Some(10),
+            }
+        );
+
+        let mut csv = Vec::new();
+        record(&mut Coordinates::default(), &commands[..2])
+            .unwrap()
+            .write_csv(&mut csv)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "step,command,horizontal_position,depth,aim\n0,,0,0,\n1,forward 5,5,0,\n2,down 5,5,5,\n"
+        );
+
+        let mut svg = Vec::new();
+        trajectory.write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains(r#"points="0.0,0.0 266.7,0.0 266.7,0.0 693.3,266.7"#));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn script() {
+        let script = "# a dive in three parts
+
+macro dive {
+    down 2 # steeper
+    forward 3
+}
+
+repeat 2 { dive repeat 2 { forward 1 } }
+back 4
+up 1";
+        let expected = "down 2\nforward 3\nforward 1\nforward 1\n".repeat(2) + "back 4\nup 1";
+        let commands = parse_input(script).unwrap();
+
+        assert_eq!(commands, parse_input(&expected).unwrap());
+        assert_eq!(commands[8], Command::new(Direction::Back, 4));
+        assert_eq!(
+            navigate(&mut AimedCoordinates::default(), &commands),
+            Ok(6 * 14)
+        );
+    }
+
+    #[test]
+    fn script_errors() {
+        let error = |script| {
+            let error = parse_input(script).unwrap_err();
+            (error.line(), error.column(), error.message().to_owned())
+        };
+
+        assert_eq!(
+            error("repeat 2 {\n  up 1\n"),
+            (1, 10, "unclosed `{`".to_owned())
+        );
+        assert_eq!(error("up 1\n}"), (2, 1, "unmatched `}`".to_owned()));
+        assert_eq!(
+            error("dive\nmacro dive { up 1 }"),
+            (1, 1, "unknown command".to_owned())
+        );
+        assert_eq!(
+            error("macro a { up 1 }\nmacro a { up 2 }"),
+            (2, 7, "macro already defined".to_owned())
+        );
+        assert_eq!(
+            error("macro up { }"),
+            (1, 7, "invalid macro name".to_owned())
+        );
+        assert_eq!(
+            error("repeat 100000 { repeat 100000 { up 1 } }"),
+            (1, 1, "the script expands to too many commands".to_owned())
+        );
+        assert_eq!(
+            error("up 1\nrepeat 18446744073709551615 { up 1 }"),
+            (2, 1, "the script expands to too many commands".to_owned())
+        );
+        assert_eq!(
+            error("forward"),
+            (1, 1, "missing units after `forward`".to_owned())
+        );
+        assert_eq!(
+            error("forward\n5"),
+            (1, 1, "missing units after `forward`".to_owned())
+        );
+        assert_eq!(
+            error("forward 5 down 3"),
+            (1, 11, "unexpected text".to_owned())
+        );
+        assert_eq!(
+            error("macro repeat { up 1 }"),
+            (1, 7, "reserved macro name".to_owned())
+        );
+        assert_eq!(
+            error("macro macro { up 1 }"),
+            (1, 7, "reserved macro name".to_owned())
+        );
+    }
+
+    #[test]
+    fn empty_repeat() {
+        assert_eq!(
+            parse_input("up 1\nrepeat 18446744073709551615 { }\ndown 2").unwrap(),
+            parse_input("up 1\ndown 2").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let error = parse_input("forward 5\nsideways 3").err().unwrap();
+
+        assert_eq!(
+            (error.line(), error.column(), error.text()),
+            (2, 1, "sideways")
+        );
     }
 }
@@ -1,108 +1,522 @@
+use crate::error::{char_at, ParseError};
+use crate::solver::{Solver, Variant};
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::cmp::Ordering::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use BitCriteria::*;
 
-#[aoc_generator(day3)]
-fn parse_input(input: &str) -> (Vec<u32>, usize) {
-    (
-        input
-            .lines()
-            .map(|l| u32::from_str_radix(l, 2).unwrap())
-            .collect(),
-        input.lines().next().unwrap().len(),
-    )
+const DAY: u8 = 3;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A diagnostic number of any width. Bits are indexed from the most significant one
+/// and packed into words from their most significant bit, so vectors of the same width
+/// compare like the numbers they stand for.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
 }
 
-#[aoc(day3, part1)]
-fn part1((entries, entry_len): &(Vec<u32>, usize)) -> u32 {
-    let mut gamma_rate = 0;
-    let mut epsilon_rate = 0;
-    let mut ones_per_bit = vec![0; *entry_len];
+impl BitVector {
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "bit {} out of {}", index, self.len);
 
-    for entry in entries {
-        for (index, count) in ones_per_bit.iter_mut().enumerate().take(*entry_len) {
-            *count += (entry >> index) & 1;
+        self.words[index / WORD_BITS] >> (WORD_BITS - 1 - index % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "bit {} out of {}", index, self.len);
+
+        let mask = 1 << (WORD_BITS - 1 - index % WORD_BITS);
+
+        if value {
+            self.words[index / WORD_BITS] |= mask;
+        } else {
+            self.words[index / WORD_BITS] &= !mask;
         }
     }
 
-    for (i, count) in ones_per_bit.iter().enumerate() {
-        let pow2 = 1 << i;
+    /// The number as 32-bit limbs, least significant first.
+    fn limbs(&self) -> Vec<u32> {
+        let mut limbs = vec![0u32; self.len.div_ceil(32)];
+
+        for index in 0..self.len {
+            let power = self.len - 1 - index;
 
-        match (count * 2).cmp(&(entries.len() as u32)) {
-            Greater => gamma_rate += pow2,
-            Less => epsilon_rate += pow2,
-            Equal => {
-                gamma_rate += pow2;
-                epsilon_rate += pow2;
+            if self.get(index) {
+                limbs[power / 32] |= 1 << (power % 32);
             }
         }
+
+        limbs
     }
+}
 
-    gamma_rate * epsilon_rate
+impl BitVector {
+    /// The number in decimal.
+    pub fn to_decimal(&self) -> String {
+        to_decimal(self.limbs())
+    }
 }
 
-enum BitCriteria {
+impl Display for BitVector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for index in 0..self.len {
+            write!(f, "{}", self.get(index) as u8)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for BitVector {
+    type Error = ParseError;
+
+    fn try_from(bits: &str) -> Result<Self, Self::Error> {
+        let mut vector = Self::zeros(bits.len());
+
+        for (index, c) in bits.char_indices() {
+            match c {
+                '0' => {}
+                '1' => vector.set(index, true),
+                _ => {
+                    return Err(ParseError::new(
+                        DAY,
+                        bits,
+                        char_at(bits, index),
+                        "invalid bit",
+                    ))
+                }
+            }
+        }
+
+        Ok(vector)
+    }
+}
+
+/// The product of two numbers of any width, in decimal.
+fn decimal_product(a: &BitVector, b: &BitVector) -> String {
+    let (a, b) = (a.limbs(), b.limbs());
+    let mut product = vec![0u32; a.len() + b.len()];
+
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+
+        for (j, &y) in b.iter().enumerate() {
+            let sum = product[i + j] as u64 + x as u64 * y as u64 + carry;
+            product[i + j] = sum as u32;
+            carry = sum >> 32;
+        }
+
+        product[i + b.len()] = carry as u32;
+    }
+
+    to_decimal(product)
+}
+
+/// Little-endian 32-bit limbs in decimal.
+fn to_decimal(mut limbs: Vec<u32>) -> String {
+    let mut digits = Vec::new();
+
+    while limbs.iter().any(|&limb| limb != 0) {
+        let mut remainder = 0u64;
+
+        for limb in limbs.iter_mut().rev() {
+            let value = remainder << 32 | *limb as u64;
+            *limb = (value / 1_000_000_000) as u32;
+            remainder = value % 1_000_000_000;
+        }
+
+        digits.push(remainder as u32);
+    }
+
+    match digits.split_last() {
+        None => "0".to_owned(),
+        Some((most_significant, rest)) => rest
+            .iter()
+            .rev()
+            .fold(most_significant.to_string(), |number, digits| {
+                format!("{}{:09}", number, digits)
+            }),
+    }
+}
+
+/// Diagnostic numbers that all have the same width.
+#[derive(Clone, Debug)]
+pub struct Report {
+    entries: Vec<BitVector>,
+    width: usize,
+}
+
+impl Report {
+    pub fn entries(&self) -> &[BitVector] {
+        &self.entries
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+#[aoc_generator(day3)]
+fn parse_input(input: &str) -> Result<Report, ParseError> {
+    let input: &str = &normalize(input);
+
+    let width = input.lines().next().map_or(0, |l| l.len());
+
+    if width == 0 {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            input.lines().next().unwrap_or(input),
+            "empty entry",
+        ));
+    }
+
+    let entries = input
+        .lines()
+        .map(|l| {
+            let entry = BitVector::try_from(l).map_err(|e| e.within(input, l))?;
+
+            if l.len() != width {
+                return Err(ParseError::new(DAY, input, l, "unexpected entry width"));
+            }
+
+            Ok(entry)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Report { entries, width })
+}
+
+/// Which bit value an entry must have to be kept while looking for a rating, or to be
+/// taken for a rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitCriteria {
     MostCommon,
     LeastCommon,
 }
 
-fn calculate_rating(sorted_entries: &[u32], entry_len: &usize, bit_criteria: BitCriteria) -> u32 {
-    let mut entries = sorted_entries.to_owned();
+/// What to do when both bit values are equally common.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TieBreak {
+    PreferOne,
+    PreferZero,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitPolicy {
+    pub criteria: BitCriteria,
+    pub tie_break: TieBreak,
+}
 
-    let mut rating = 0;
-    let mut pow2 = 1 << (*entry_len - 1);
+impl BitPolicy {
+    pub fn new(criteria: BitCriteria, tie_break: TieBreak) -> Self {
+        Self {
+            criteria,
+            tie_break,
+        }
+    }
 
-    while entries.len() > 1 {
-        let middle = entries[entries.len() / 2];
-        let leading_digit = middle / pow2;
+    fn choose(&self, column: usize, zeros: usize, ones: usize) -> Result<bool, TieError> {
+        match (zeros.cmp(&ones), self.criteria, self.tie_break) {
+            (Equal, _, TieBreak::PreferOne) => Ok(true),
+            (Equal, _, TieBreak::PreferZero) => Ok(false),
+            (Equal, _, TieBreak::Error) => Err(TieError { column }),
+            (ordering, MostCommon, _) => Ok(ordering == Less),
+            (ordering, LeastCommon, _) => Ok(ordering == Greater),
+        }
+    }
+}
 
-        match (leading_digit, &bit_criteria) {
-            (1, MostCommon) | (0, LeastCommon) => rating += pow2,
-            _ => {}
-        };
+/// Both bit values are equally common in a column whose policy does not allow ties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TieError {
+    /// The column of the bit, from 0 for the most significant one.
+    pub column: usize,
+}
 
-        entries = entries
-            .iter()
-            .filter(|&entry| match (leading_digit, &bit_criteria) {
-                (0, MostCommon) => *entry < pow2,
-                (1, MostCommon) => *entry >= pow2,
-                (0, LeastCommon) => *entry >= pow2,
-                (1, LeastCommon) => *entry < pow2,
-                _ => unreachable!(),
-            })
-            .map(|entry| entry % pow2)
-            .collect();
+impl Display for TieError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "zeros and ones are tied in column {}", self.column)
+    }
+}
+
+impl Error for TieError {}
+
+/// A policy for every column of a report: a default one, and overrides for some
+/// columns, numbered from 0 for the most significant bit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Criteria {
+    default: BitPolicy,
+    columns: HashMap<usize, BitPolicy>,
+}
 
-        pow2 /= 2;
+impl Criteria {
+    pub fn new(default: BitPolicy) -> Self {
+        Self {
+            default,
+            columns: HashMap::new(),
+        }
     }
 
-    rating += entries[0];
+    pub fn with_column(mut self, column: usize, policy: BitPolicy) -> Self {
+        self.columns.insert(column, policy);
+        self
+    }
 
-    rating
+    pub fn policy(&self, column: usize) -> BitPolicy {
+        self.columns.get(&column).copied().unwrap_or(self.default)
+    }
 }
 
-fn calculate_oxygen_generator_rating(sorted_entries: &[u32], entry_len: &usize) -> u32 {
-    calculate_rating(sorted_entries, entry_len, MostCommon)
+/// The order in which the bits of the entries are looked at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    #[default]
+    MostSignificantFirst,
+    LeastSignificantFirst,
 }
 
-fn calculate_co2_scrubber_rating(sorted_entries: &[u32], entry_len: &usize) -> u32 {
-    calculate_rating(sorted_entries, entry_len, LeastCommon)
+impl BitOrder {
+    /// The column looked at in step `step` for entries of `width` bits.
+    fn column(self, step: usize, width: usize) -> usize {
+        match self {
+            BitOrder::MostSignificantFirst => step,
+            BitOrder::LeastSignificantFirst => width - 1 - step,
+        }
+    }
+}
+
+impl Report {
+    /// Takes the bit of each column chosen by its policy among all entries.
+    pub fn rate(&self, criteria: &Criteria) -> Result<BitVector, TieError> {
+        let mut rate = BitVector::zeros(self.width);
+
+        for column in 0..self.width {
+            let ones = self
+                .entries
+                .iter()
+                .filter(|entry| entry.get(column))
+                .count();
+            let bit = criteria
+                .policy(column)
+                .choose(column, self.entries.len() - ones, ones)?;
+
+            rate.set(column, bit);
+        }
+
+        Ok(rate)
+    }
+}
+
+/// Ties count for both rates, following the puzzle.
+#[aoc(day3, part1)]
+fn part1(report: &Report) -> String {
+    let gamma_rate = report.rate(&Criteria::new(BitPolicy::new(
+        MostCommon,
+        TieBreak::PreferOne,
+    )));
+    let epsilon_rate = report.rate(&Criteria::new(BitPolicy::new(
+        LeastCommon,
+        TieBreak::PreferOne,
+    )));
+
+    decimal_product(&gamma_rate.unwrap(), &epsilon_rate.unwrap())
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    children: [Option<usize>; 2],
+    count: usize,
+}
+
+/// The entries of a report in a binary trie, with the number of entries below each
+/// node, answering rating queries in time proportional to the width of the entries.
+/// Each level of the trie holds the bits of one column, in the order given.
+#[derive(Clone, Debug)]
+pub struct Trie {
+    nodes: Vec<Node>,
+    width: usize,
+    order: BitOrder,
+}
+
+impl From<&Report> for Trie {
+    fn from(report: &Report) -> Self {
+        Self::new(report, BitOrder::default())
+    }
+}
+
+impl Trie {
+    pub fn new(report: &Report, order: BitOrder) -> Self {
+        let mut nodes = vec![Node::default()];
+
+        for entry in report.entries.iter() {
+            let mut node = 0;
+            nodes[node].count += 1;
+
+            for step in 0..report.width {
+                let digit = entry.get(order.column(step, report.width)) as usize;
+
+                node = match nodes[node].children[digit] {
+                    Some(child) => child,
+                    None => {
+                        nodes.push(Node::default());
+                        nodes[node].children[digit] = Some(nodes.len() - 1);
+                        nodes.len() - 1
+                    }
+                };
+                nodes[node].count += 1;
+            }
+        }
+
+        Self {
+            nodes,
+            width: report.width,
+            order,
+        }
+    }
+
+    fn count(&self, child: Option<usize>) -> usize {
+        child.map_or(0, |child| self.nodes[child].count)
+    }
+
+    /// Keeps the entries whose bit in each column, in the order of the trie, is chosen
+    /// by the policy of the column, until a single entry is left. When every entry left
+    /// has the same bit, they are all kept.
+    pub fn rating(&self, criteria: &Criteria) -> Result<BitVector, TieError> {
+        let mut rating = BitVector::zeros(self.width);
+        let mut node = 0;
+
+        for step in 0..self.width {
+            let column = self.order.column(step, self.width);
+            let [zero, one] = self.nodes[node].children;
+
+            let digit = match (self.count(zero), self.count(one)) {
+                (0, _) => true,
+                (_, 0) => false,
+                (zeros, ones) => criteria.policy(column).choose(column, zeros, ones)?,
+            };
+
+            rating.set(column, digit);
+            node = self.nodes[node].children[digit as usize].unwrap();
+        }
+
+        Ok(rating)
+    }
+}
+
+fn calculate_oxygen_generator_rating(trie: &Trie) -> BitVector {
+    trie.rating(&Criteria::new(BitPolicy::new(
+        MostCommon,
+        TieBreak::PreferOne,
+    )))
+    .unwrap()
+}
+
+fn calculate_co2_scrubber_rating(trie: &Trie) -> BitVector {
+    trie.rating(&Criteria::new(BitPolicy::new(
+        LeastCommon,
+        TieBreak::PreferZero,
+    )))
+    .unwrap()
 }
 
 #[aoc(day3, part2)]
-fn part2((entries, len): &(Vec<u32>, usize)) -> u32 {
-    let mut sorted_entries = entries.to_owned();
+fn part2(report: &Report) -> String {
+    let trie = Trie::from(report);
+
+    let oxygen_generator_rating = calculate_oxygen_generator_rating(&trie);
+    let co2_scrubber_rating = calculate_co2_scrubber_rating(&trie);
+
+    decimal_product(&oxygen_generator_rating, &co2_scrubber_rating)
+}
+
+fn filter_rating(sorted_entries: &[BitVector], bit_criteria: BitCriteria) -> BitVector {
+    let mut entries = sorted_entries.iter().collect::<Vec<_>>();
+    let mut bit = 0;
+
+    while entries.len() > 1 && bit < entries[0].len() {
+        let middle = entries[entries.len() / 2];
+        let leading_digit = middle.get(bit);
+
+        let kept_digit = match bit_criteria {
+            MostCommon => leading_digit,
+            LeastCommon => !leading_digit,
+        };
+
+        if entries.iter().any(|entry| entry.get(bit) == kept_digit) {
+            entries.retain(|entry| entry.get(bit) == kept_digit);
+        }
+
+        bit += 1;
+    }
+
+    entries[0].to_owned()
+}
+
+#[aoc(day3, part2, filter)]
+fn part2_filter(report: &Report) -> String {
+    let mut sorted_entries = report.entries.to_owned();
     sorted_entries.sort_unstable();
 
-    let oxygen_generator_rating = calculate_oxygen_generator_rating(&sorted_entries, len);
-    let co2_scrubber_rating = calculate_co2_scrubber_rating(&sorted_entries, len);
+    decimal_product(
+        &filter_rating(&sorted_entries, MostCommon),
+        &filter_rating(&sorted_entries, LeastCommon),
+    )
+}
+
+/// Day 3: Binary Diagnostic.
+pub struct Day03;
+
+impl Solver for Day03 {
+    const DAY: u8 = DAY;
 
-    oxygen_generator_rating * co2_scrubber_rating
+    type Input = Report;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+
+    fn part2_variants() -> Vec<Variant<Self::Input, Self::Part2>> {
+        vec![("filter", |report| part2_filter(report))]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::property::{check, no_shrink, shrink_vec};
 
     static TEST_INPUT: &str = r"00100
 11110
@@ -119,11 +533,122 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 198);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), "198");
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 230);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), "230");
+    }
+
+    #[test]
+    fn part2_filter_example() {
+        assert_eq!(part2_filter(&parse_input(TEST_INPUT).unwrap()), "230");
+    }
+
+    #[test]
+    fn rating_queries() {
+        let trie = Trie::from(&parse_input(TEST_INPUT).unwrap());
+        let rating = |criteria, tie_break| {
+            trie.rating(&Criteria::new(BitPolicy::new(criteria, tie_break)))
+                .map(|rating| rating.to_string())
+        };
+
+        assert_eq!(
+            rating(MostCommon, TieBreak::PreferOne),
+            Ok("10111".to_owned())
+        );
+        assert_eq!(
+            rating(LeastCommon, TieBreak::PreferZero),
+            Ok("01010".to_owned())
+        );
+        assert_eq!(
+            rating(MostCommon, TieBreak::PreferZero),
+            Ok("10110".to_owned())
+        );
+        assert_eq!(
+            rating(LeastCommon, TieBreak::PreferOne),
+            Ok("01111".to_owned())
+        );
+        assert_eq!(
+            rating(MostCommon, TieBreak::Error),
+            Err(TieError { column: 4 })
+        );
+    }
+
+    #[test]
+    fn configurable_criteria() {
+        let report = parse_input(TEST_INPUT).unwrap();
+        let most = BitPolicy::new(MostCommon, TieBreak::Error);
+        let least = BitPolicy::new(LeastCommon, TieBreak::PreferZero);
+
+        // Column 0 has seven ones and five zeros.
+        let criteria = Criteria::new(most).with_column(0, least);
+        assert_eq!(report.rate(&criteria).unwrap().to_string(), "00110");
+
+        let trie = Trie::new(&report, BitOrder::LeastSignificantFirst);
+        assert_eq!(trie.rating(&criteria), Err(TieError { column: 2 }));
+
+        let criteria = Criteria::new(BitPolicy::new(MostCommon, TieBreak::PreferOne));
+        assert_eq!(trie.rating(&criteria).unwrap().to_string(), "11110");
+
+        let criteria = Criteria::new(most).with_column(0, least);
+        let tied = parse_input("01\n10").unwrap();
+        assert_eq!(tied.rate(&criteria), Err(TieError { column: 1 }));
+        assert_eq!(
+            Trie::from(&tied).rating(&criteria).unwrap().to_string(),
+            "01"
+        );
+    }
+
+    #[test]
+    fn trie_matches_filter() {
+        check(
+            3,
+            |rng| {
+                let width = rng.range(1, 8) as usize;
+                (0..rng.range(1, 20))
+                    .map(|_| {
+                        (0..width)
+                            .map(|_| *rng.choose(&['0', '1']))
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |entries| shrink_vec(entries, 1, no_shrink),
+            |entries| {
+                let report = parse_input(&entries.join("\n")).unwrap();
+                part2(&report) == part2_filter(&report)
+            },
+        );
+    }
+
+    #[test]
+    fn wide_entries() {
+        let ones = "1".repeat(100);
+        let zeros = "0".repeat(100);
+        let report = parse_input(&format!("{}\n{}\n{}", ones, zeros, ones)).unwrap();
+
+        // The rates and the ratings are 2^100 - 1 and 0.
+        assert_eq!(part1(&report), "0");
+        assert_eq!(part2(&report), "0");
+        assert_eq!(
+            // (2^100 - 1)^2
+            decimal_product(&report.entries[0], &report.entries[0]),
+            "1606938044258990275541962092338627301321746534979799428890625"
+        );
+        assert!(report.entries[1] < report.entries[0]);
+    }
+
+    #[test]
+    fn invalid_reports() {
+        let error = parse_input("0101\n011\n0111").unwrap_err();
+        assert_eq!(
+            (error.line(), error.message()),
+            (2, "unexpected entry width")
+        );
+
+        let error = parse_input("0101\n0121").unwrap_err();
+        assert_eq!((error.line(), error.column(), error.text()), (2, 3, "2"));
     }
 }
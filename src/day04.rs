@@ -1,14 +1,22 @@
+use crate::error::{next_part, parse_number, ParseError};
+use crate::json::Json;
+use crate::solver::Solver;
+use crate::text::{normalize, paragraphs};
 use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
 use BoardState::*;
 use CellState::*;
 
+const DAY: u8 = 4;
+
 #[derive(Debug, Clone, PartialEq)]
 enum CellState {
     Unmarked,
     Marked,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct Position {
     row: usize,
     column: usize,
@@ -16,7 +24,7 @@ struct Position {
 
 #[derive(Debug, Clone)]
 struct Cell {
-    number: u8,
+    number: u32,
     position: Position,
     state: CellState,
 }
@@ -27,22 +35,118 @@ enum BoardState {
     Won,
 }
 
+/// A rectangular board of unique numbers, stored in row-major order.
 #[derive(Debug, Clone)]
-struct Board {
+pub struct Board {
     cells: Vec<Cell>,
+    width: usize,
+    height: usize,
     state: BoardState,
 }
 
-impl Board {
-    fn make_turn(&mut self, number: &u8) -> BoardState {
-        if let Some(position) = self.mark_number(number) {
-            self.update_state(&position);
+/// A set of cells that wins a board once all of them are marked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    /// Any full row.
+    Rows,
+    /// Any full column.
+    Columns,
+    /// Either full diagonal of a square board; other boards have no diagonals.
+    Diagonals,
+    /// The four corner cells.
+    FourCorners,
+    /// Every cell on the board.
+    Blackout,
+    /// The given `(row, column)` cells. A mask reaching outside a board, or an empty
+    /// one, never wins on it.
+    Mask(Vec<(usize, usize)>),
+}
+
+impl Pattern {
+    /// A mask drawn with `#` for the cells it covers, one line per row; any other
+    /// character is skipped over.
+    pub fn mask(drawing: &str) -> Self {
+        Pattern::Mask(
+            drawing
+                .lines()
+                .enumerate()
+                .flat_map(|(row, line)| {
+                    line.chars()
+                        .enumerate()
+                        .filter(|(_, c)| *c == '#')
+                        .map(move |(column, _)| (row, column))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The patterns that win a board; a board wins as soon as any of them is complete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules(Vec<Pattern>);
+
+impl Rules {
+    pub fn new(patterns: Vec<Pattern>) -> Self {
+        Self(patterns)
+    }
+}
+
+/// Rows and columns, as in the puzzle.
+impl Default for Rules {
+    fn default() -> Self {
+        Self(vec![Pattern::Rows, Pattern::Columns])
+    }
+}
+
+/// The pattern that completed a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bingo {
+    Row(usize),
+    Column(usize),
+    /// From the top left corner to the bottom right one.
+    Diagonal,
+    /// From the top right corner to the bottom left one.
+    AntiDiagonal,
+    FourCorners,
+    Blackout,
+    /// Index of the mask in the rules.
+    Mask(usize),
+}
+
+impl Display for Bingo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Bingo::Row(row) => write!(f, "row {}", row),
+            Bingo::Column(column) => write!(f, "column {}", column),
+            Bingo::Diagonal => write!(f, "diagonal"),
+            Bingo::AntiDiagonal => write!(f, "anti-diagonal"),
+            Bingo::FourCorners => write!(f, "four corners"),
+            Bingo::Blackout => write!(f, "blackout"),
+            Bingo::Mask(index) => write!(f, "mask {}", index),
         }
+    }
+}
 
-        self.state.to_owned()
+impl Board {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Marks `number` and returns the pattern it completes, if any.
+    fn make_turn(&mut self, number: &u32, rules: &Rules) -> Option<Bingo> {
+        let position = self.mark_number(number)?;
+        let bingo = self.completed_pattern(&position, rules)?;
+
+        self.state = Won;
+
+        Some(bingo)
     }
 
-    fn mark_number(&mut self, number: &u8) -> Option<Position> {
+    fn mark_number(&mut self, number: &u32) -> Option<Position> {
         self.cells
             .iter_mut()
             .filter(|cell| cell.number == *number)
@@ -53,115 +157,266 @@ impl Board {
             .next()
     }
 
-    fn update_state(&mut self, position: &Position) {
-        if !self
-            .cells
+    fn is_marked(&self, row: usize, column: usize) -> bool {
+        row < self.height
+            && column < self.width
+            && self.cells[row * self.width + column].state == Marked
+    }
+
+    /// The first pattern of `rules` completed by marking `position`. Only patterns
+    /// through `position` are checked, as the board had not won before.
+    fn completed_pattern(&self, position: &Position, rules: &Rules) -> Option<Bingo> {
+        let &Position { row, column } = position;
+        let last = (self.height - 1, self.width - 1);
+
+        rules
+            .0
             .iter()
-            .any(|c| c.position.column == position.column && c.state == Unmarked)
-            || !self
-                .cells
-                .iter()
-                .any(|c| c.position.row == position.row && c.state == Unmarked)
-        {
-            self.state = Won;
-        }
+            .enumerate()
+            .find_map(|(index, pattern)| match pattern {
+                Pattern::Rows => (0..self.width)
+                    .all(|c| self.is_marked(row, c))
+                    .then_some(Bingo::Row(row)),
+                Pattern::Columns => (0..self.height)
+                    .all(|r| self.is_marked(r, column))
+                    .then_some(Bingo::Column(column)),
+                Pattern::Diagonals if self.width == self.height => {
+                    if row == column && (0..self.width).all(|i| self.is_marked(i, i)) {
+                        Some(Bingo::Diagonal)
+                    } else if row + column == last.1
+                        && (0..self.width).all(|i| self.is_marked(i, last.1 - i))
+                    {
+                        Some(Bingo::AntiDiagonal)
+                    } else {
+                        None
+                    }
+                }
+                Pattern::Diagonals => None,
+                Pattern::FourCorners => ((row == 0 || row == last.0)
+                    && (column == 0 || column == last.1)
+                    && [(0, 0), (0, last.1), (last.0, 0), last]
+                        .iter()
+                        .all(|&(r, c)| self.is_marked(r, c)))
+                .then_some(Bingo::FourCorners),
+                Pattern::Blackout => self
+                    .cells
+                    .iter()
+                    .all(|c| c.state == Marked)
+                    .then_some(Bingo::Blackout),
+                Pattern::Mask(cells) => (cells.contains(&(row, column))
+                    && cells.iter().all(|&(r, c)| self.is_marked(r, c)))
+                .then_some(Bingo::Mask(index)),
+            })
     }
 
-    fn calculate_score(&self, last_number: &u8) -> u32 {
+    fn calculate_score(&self, last_number: &u32) -> u64 {
         self.cells
             .iter()
             .filter(|&c| c.state == Unmarked)
-            .map(|c| c.number as u32)
-            .sum::<u32>()
-            * *last_number as u32
+            .map(|c| c.number as u64)
+            .sum::<u64>()
+            * *last_number as u64
     }
 }
 
-impl From<&str> for Board {
-    fn from(board_str: &str) -> Self {
-        Board {
-            cells: board_str
-                .lines()
-                .enumerate()
-                .flat_map(|(row_num, row)| {
-                    row.split_whitespace()
-                        .enumerate()
-                        .map(|(column_num, n)| Cell {
-                            number: n.parse().unwrap(),
-                            position: Position {
-                                row: row_num,
-                                column: column_num,
-                            },
-                            state: Unmarked,
-                        })
-                        .collect::<Vec<Cell>>()
-                })
-                .collect(),
-            state: Playing,
+impl TryFrom<&str> for Board {
+    type Error = ParseError;
+
+    fn try_from(board_str: &str) -> Result<Self, Self::Error> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut numbers = HashSet::new();
+
+        for (row_num, row) in board_str.lines().enumerate() {
+            let row_width = row.split_whitespace().count();
+
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(ParseError::new(DAY, board_str, row, "unexpected row width"));
+            }
+
+            for (column_num, n) in row.split_whitespace().enumerate() {
+                let number = parse_number(DAY, board_str, n)?;
+
+                if !numbers.insert(number) {
+                    return Err(ParseError::new(DAY, board_str, n, "duplicate number"));
+                }
+
+                cells.push(Cell {
+                    number,
+                    position: Position {
+                        row: row_num,
+                        column: column_num,
+                    },
+                    state: Unmarked,
+                });
+            }
+        }
+
+        if cells.is_empty() {
+            return Err(ParseError::new(DAY, board_str, board_str, "empty board"));
         }
+
+        let width = width.unwrap_or_default();
+
+        Ok(Board {
+            height: cells.len() / width,
+            width,
+            cells,
+            state: Playing,
+        })
     }
 }
 
-struct DrawOrder(Vec<u8>);
+pub struct DrawOrder(Vec<u32>);
+
+impl TryFrom<&str> for DrawOrder {
+    type Error = ParseError;
 
-impl From<&str> for DrawOrder {
-    fn from(draw_order_str: &str) -> Self {
-        Self(
+    fn try_from(draw_order_str: &str) -> Result<Self, Self::Error> {
+        Ok(Self(
             draw_order_str
                 .split(',')
-                .map(|number| number.parse().unwrap())
-                .collect(),
-        )
+                .map(|number| parse_number(DAY, draw_order_str, number))
+                .collect::<Result<_, _>>()?,
+        ))
     }
 }
 
 #[aoc_generator(day4)]
-fn parse_input(input: &str) -> (DrawOrder, Vec<Board>) {
-    let mut split = input.split("\n\n");
+fn parse_input(input: &str) -> Result<(DrawOrder, Vec<Board>), ParseError> {
+    let input: &str = &normalize(input);
 
-    let draw_order = split.next().unwrap().into();
-    let boards = split.map(|b| b.into()).collect();
+    let mut split = paragraphs(input);
 
-    (draw_order, boards)
+    let draw_order_str = next_part(DAY, input, &mut split, "draw order")?;
+    let draw_order =
+        DrawOrder::try_from(draw_order_str).map_err(|e| e.within(input, draw_order_str))?;
+    let boards = split
+        .map(|b| Board::try_from(b).map_err(|e| e.within(input, b)))
+        .collect::<Result<_, _>>()?;
+
+    Ok((draw_order, boards))
 }
 
-#[aoc(day4, part1)]
-fn part1((draw_order, boards): &(DrawOrder, Vec<Board>)) -> Option<u32> {
-    let mut boards = (*boards).clone();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Win {
+    /// Index of the board in the input.
+    pub board: usize,
+    /// Index of the winning number in the draw order.
+    pub draw: usize,
+    pub number: u32,
+    pub score: u64,
+    pub bingo: Bingo,
+}
 
-    for number in draw_order.0.iter() {
-        for board in boards.iter_mut() {
-            if board.make_turn(number) == Won {
-                return Some(board.calculate_score(number));
-            }
-        }
-    }
+/// The outcome of playing the whole draw order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    /// Wins in the order they happen; boards winning on the same number are in input
+    /// order.
+    pub wins: Vec<Win>,
+    /// Indices of the boards that never win.
+    pub never_won: Vec<usize>,
+}
 
-    None
+/// Plays with the default rules.
+pub fn play(draw_order: &DrawOrder, boards: &[Board]) -> Game {
+    play_with(draw_order, boards, &Rules::default())
 }
 
-#[aoc(day4, part2)]
-fn part2((draw_order, boards): &(DrawOrder, Vec<Board>)) -> Option<u32> {
-    let mut boards = (*boards).clone();
-    let mut playing_count = boards.len();
+/// Draws every number in order, marking it on every board that has not won yet.
+pub fn play_with(draw_order: &DrawOrder, boards: &[Board], rules: &Rules) -> Game {
+    let mut boards = boards.to_owned();
+    let mut wins = Vec::new();
 
-    for number in draw_order.0.iter() {
-        for board in boards.iter_mut() {
+    for (draw, number) in draw_order.0.iter().enumerate() {
+        if wins.len() == boards.len() {
+            break;
+        }
+
+        for (index, board) in boards.iter_mut().enumerate() {
             if board.state == Won {
                 continue;
             }
 
-            if board.make_turn(number) == Won {
-                playing_count -= 1;
-
-                if playing_count == 0 {
-                    return Some(board.calculate_score(number));
-                }
+            if let Some(bingo) = board.make_turn(number, rules) {
+                wins.push(Win {
+                    board: index,
+                    draw,
+                    number: *number,
+                    score: board.calculate_score(number),
+                    bingo,
+                });
             }
         }
     }
 
-    None
+    let never_won = boards
+        .iter()
+        .enumerate()
+        .filter(|(_, board)| board.state == Playing)
+        .map(|(index, _)| index)
+        .collect();
+
+    Game { wins, never_won }
+}
+
+#[aoc(day4, part1)]
+fn part1((draw_order, boards): &(DrawOrder, Vec<Board>)) -> Option<u64> {
+    play(draw_order, boards).wins.first().map(|win| win.score)
+}
+
+#[aoc(day4, part2)]
+fn part2((draw_order, boards): &(DrawOrder, Vec<Board>)) -> Option<u64> {
+    let game = play(draw_order, boards);
+
+    if !game.never_won.is_empty() {
+        return None;
+    }
+
+    game.wins.last().map(|win| win.score)
+}
+
+/// Day 4: Giant Squid.
+pub struct Day04;
+
+impl Solver for Day04 {
+    const DAY: u8 = DAY;
+
+    type Input = (DrawOrder, Vec<Board>);
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+
+    fn details((draw_order, boards): &Self::Input) -> Vec<(&'static str, Json)> {
+        let game = play(draw_order, boards);
+        let wins = game
+            .wins
+            .iter()
+            .map(|win| {
+                Json::object([
+                    ("board", win.board.into()),
+                    ("draw", win.draw.into()),
+                    ("number", win.number.into()),
+                    ("score", win.score.into()),
+                    ("bingo", win.bingo.to_string().into()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        vec![("wins", wins.into()), ("never_won", game.never_won.into())]
+    }
 }
 
 #[cfg(test)]
@@ -190,11 +445,130 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), Some(4512));
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), Some(4512));
+    }
+
+    #[test]
+    fn crlf_input() {
+        let input = format!("{}\r\n\r\n", TEST_INPUT.replace('\n', "\r\n"));
+
+        assert_eq!(part1(&parse_input(&input).unwrap()), Some(4512));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), Some(1924));
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), Some(1924));
+    }
+
+    #[test]
+    fn win_order() {
+        let (draw_order, mut boards) = parse_input(TEST_INPUT).unwrap();
+        boards.push(Board::try_from("99 98").unwrap());
+
+        let game = play(&draw_order, &boards);
+
+        assert_eq!(
+            game.wins,
+            vec![
+                Win {
+                    board: 2,
+                    draw: 11,
+                    number: 24,
+                    score: 4512,
+                    bingo: Bingo::Row(0),
+                },
+                Win {
+                    board: 0,
+                    draw: 13,
+                    number: 16,
+                    score: 2192,
+                    bingo: Bingo::Row(2),
+                },
+                Win {
+                    board: 1,
+                    draw: 14,
+                    number: 13,
+                    score: 1924,
+                    bingo: Bingo::Column(2),
+                },
+            ]
+        );
+        assert_eq!(game.never_won, vec![3]);
+        assert_eq!(part2(&(draw_order, boards)), None);
+    }
+
+    fn draws(numbers: &[u32]) -> DrawOrder {
+        DrawOrder(numbers.to_vec())
+    }
+
+    #[test]
+    fn diagonals() {
+        let boards = [Board::try_from("1 2 3\n4 5 6\n7 8 9").unwrap()];
+        let rules = Rules::new(vec![Pattern::Diagonals]);
+
+        let game = play_with(&draws(&[1, 3, 5, 7, 9]), &boards, &rules);
+        assert_eq!(game.wins[0].bingo, Bingo::AntiDiagonal);
+        assert_eq!(game.wins[0].score, 29 * 7);
+
+        let game = play_with(&draws(&[1, 2, 4, 9, 5]), &boards, &rules);
+        assert_eq!(game.wins[0].bingo, Bingo::Diagonal);
+    }
+
+    #[test]
+    fn non_square_board() {
+        let boards = [Board::try_from("1 2 3\n4 5 6").unwrap()];
+        let order = draws(&[1, 5, 3, 2, 4, 6]);
+
+        assert_eq!((boards[0].width(), boards[0].height()), (3, 2));
+        assert_eq!(play(&order, &boards).wins[0].bingo, Bingo::Row(0));
+
+        let diagonals = Rules::new(vec![Pattern::Diagonals]);
+        assert_eq!(play_with(&order, &boards, &diagonals).never_won, vec![0]);
+
+        let corners = Rules::new(vec![Pattern::FourCorners]);
+        let win = play_with(&order, &boards, &corners).wins[0];
+        assert_eq!((win.draw, win.bingo, win.score), (5, Bingo::FourCorners, 0));
+
+        let blackout = Rules::new(vec![Pattern::Rows, Pattern::Blackout]);
+        let game = play_with(&draws(&[1, 5]), &boards, &blackout);
+        assert_eq!(game.never_won, vec![0]);
+    }
+
+    #[test]
+    fn blackout() {
+        let (draw_order, boards) = parse_input(TEST_INPUT).unwrap();
+        let rules = Rules::new(vec![Pattern::Blackout]);
+
+        let game = play_with(&draw_order, &boards, &rules);
+
+        assert_eq!(game.wins.len(), 3);
+        assert!(game.wins.iter().all(|win| win.bingo == Bingo::Blackout));
+        assert!(game.wins.iter().all(|win| win.score == 0));
+    }
+
+    #[test]
+    fn mask() {
+        let board = Board::try_from("1 2 3\n4 5 6").unwrap();
+        let rules = Rules::new(vec![
+            Pattern::Columns,
+            Pattern::mask("#..\n.#."),
+            Pattern::mask("...\n...\n#.."),
+        ]);
+
+        let game = play_with(&draws(&[5, 1, 2, 4]), &[board], &rules);
+
+        assert_eq!(game.wins[0].draw, 1);
+        assert_eq!(game.wins[0].bingo, Bingo::Mask(1));
+        assert_eq!(game.wins[0].score, (2 + 3 + 4 + 6));
+    }
+
+    #[test]
+    fn board_errors() {
+        let error = Board::try_from("1 2 3\n4 5").unwrap_err();
+        assert_eq!((error.line(), error.message()), (2, "unexpected row width"));
+
+        let error = Board::try_from("1 2\n3 1").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(error.message(), "duplicate number");
     }
 }
@@ -1,25 +1,16 @@
 use crate::day05::SegmentOrientation::*;
 use crate::day05::TriangleOrientation::*;
+use crate::error::{next_part, ParseError};
+use crate::geometry::Vec2;
+use crate::solver::{Solver, Variant};
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::cmp::Ordering::*;
 use std::collections::{HashMap, HashSet};
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl From<&str> for Point {
-    fn from(point_str: &str) -> Self {
-        let mut iter = point_str.split(',');
+const DAY: u8 = 5;
 
-        Self {
-            x: iter.next().unwrap().parse().unwrap(),
-            y: iter.next().unwrap().parse().unwrap(),
-        }
-    }
-}
+type Point = Vec2<i32>;
 
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 enum SegmentOrientation {
@@ -30,26 +21,43 @@ enum SegmentOrientation {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-struct Segment {
+pub struct Segment {
     start: Point,
     end: Point,
     orientation: SegmentOrientation,
 }
 
-impl From<&str> for Segment {
-    fn from(segment_str: &str) -> Self {
+impl TryFrom<&str> for Segment {
+    type Error = ParseError;
+
+    fn try_from(segment_str: &str) -> Result<Self, Self::Error> {
         let mut iter = segment_str.split(" -> ");
 
-        let start: Point = iter.next().unwrap().into();
-        let end: Point = iter.next().unwrap().into();
+        let start_str = next_part(DAY, segment_str, &mut iter, "start")?;
+        let start =
+            Point::from_csv(DAY, start_str).map_err(|e| e.within(segment_str, start_str))?;
+        let end_str = next_part(DAY, segment_str, &mut iter, "end")?;
+        let end = Point::from_csv(DAY, end_str).map_err(|e| e.within(segment_str, end_str))?;
+
+        if start.x != end.x
+            && start.y != end.y
+            && (end.x - start.x).abs() != (end.y - start.y).abs()
+        {
+            return Err(ParseError::new(
+                DAY,
+                segment_str,
+                segment_str,
+                "segment is neither straight nor diagonal",
+            ));
+        }
 
         let orientation = Self::calculate_orientation(&start, &end);
 
-        Self {
+        Ok(Self {
             start,
             end,
             orientation,
-        }
+        })
     }
 }
 
@@ -69,25 +77,21 @@ impl Segment {
     fn into_points(self) -> Vec<Point> {
         let mut points = Vec::new();
 
-        let x_increment = -(self.start.x.cmp(&self.end.x) as i32);
-        let y_increment = -(self.start.y.cmp(&self.end.y) as i32);
+        let increment = Point::new(
+            -(self.start.x.cmp(&self.end.x) as i32),
+            -(self.start.y.cmp(&self.end.y) as i32),
+        );
 
-        let mut point = Point {
-            x: self.start.x,
-            y: self.start.y,
-        };
+        let mut point = self.start;
 
         loop {
-            points.push(point.to_owned());
+            points.push(point);
 
             if point == self.end {
                 break;
             }
 
-            point = Point {
-                x: point.x + x_increment,
-                y: point.y + y_increment,
-            };
+            point += increment;
         }
 
         points
@@ -183,25 +187,25 @@ fn intersect(segment0: &Segment, segment1: &Segment) -> HashSet<Point> {
     }
 
     if segment1_start_to_segment0 == Collinear && point_inside_box(&segment1.start, segment0) {
-        overlap_points.insert(segment1.start.to_owned());
+        overlap_points.insert(segment1.start);
     }
 
     if segment1_end_to_segment0 == Collinear && point_inside_box(&segment1.end, segment0) {
-        overlap_points.insert(segment1.end.to_owned());
+        overlap_points.insert(segment1.end);
     }
 
     if segment0_start_to_segment1 == Collinear && point_inside_box(&segment0.start, segment1) {
-        overlap_points.insert(segment0.start.to_owned());
+        overlap_points.insert(segment0.start);
     }
 
     if segment0_end_to_segment1 == Collinear && point_inside_box(&segment0.end, segment1) {
-        overlap_points.insert(segment0.end.to_owned());
+        overlap_points.insert(segment0.end);
     }
 
     if overlap_points.len() > 1 {
         let mut iter = overlap_points.iter();
-        let start = iter.next().unwrap().to_owned();
-        let end = iter.next().unwrap().to_owned();
+        let start = *iter.next().unwrap();
+        let end = *iter.next().unwrap();
 
         let orientation = Segment::calculate_orientation(&start, &end);
 
@@ -219,8 +223,13 @@ fn intersect(segment0: &Segment, segment1: &Segment) -> HashSet<Point> {
 }
 
 #[aoc_generator(day5)]
-fn parse_input(input: &str) -> Vec<Segment> {
-    input.lines().map(|l| l.into()).collect()
+fn parse_input(input: &str) -> Result<Vec<Segment>, ParseError> {
+    let input: &str = &normalize(input);
+
+    input
+        .lines()
+        .map(|l| Segment::try_from(l).map_err(|e| e.within(input, l)))
+        .collect()
 }
 
 #[aoc(day5, part1)]
@@ -293,9 +302,42 @@ fn part2_all_points(segments: &[Segment]) -> usize {
     overlap_points.values().filter(|&count| *count > 1).count()
 }
 
+/// Day 5: Hydrothermal Venture.
+pub struct Day05;
+
+impl Solver for Day05 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Segment>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+
+    fn part1_variants() -> Vec<Variant<Self::Input, Self::Part1>> {
+        vec![("all_points", |segments| part1_all_points(segments))]
+    }
+
+    fn part2_variants() -> Vec<Variant<Self::Input, Self::Part2>> {
+        vec![("all_points", |segments| part2_all_points(segments))]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::Rng;
+    use crate::property::{check, no_shrink, shrink_vec};
 
     static TEST_INPUT: &str = r"0,9 -> 5,9
 8,0 -> 0,8
@@ -310,21 +352,80 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 5);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 5);
     }
 
     #[test]
     fn part1_all_points_example() {
-        assert_eq!(part1_all_points(&parse_input(TEST_INPUT)), 5);
+        assert_eq!(part1_all_points(&parse_input(TEST_INPUT).unwrap()), 5);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 12);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 12);
     }
 
     #[test]
     fn part2_all_points_example() {
-        assert_eq!(part2_all_points(&parse_input(TEST_INPUT)), 12);
+        assert_eq!(part2_all_points(&parse_input(TEST_INPUT).unwrap()), 12);
+    }
+
+    const SIZE: i64 = 10;
+
+    type Line = (i64, i64, i64, i64);
+
+    fn line(rng: &mut Rng) -> Line {
+        let (x1, y1) = (rng.range(0, SIZE - 1), rng.range(0, SIZE - 1));
+        let (dx, dy) = (rng.range(-1, 1), rng.range(-1, 1));
+        let length = (0..SIZE)
+            .take_while(|n| {
+                (0..SIZE).contains(&(x1 + dx * n)) && (0..SIZE).contains(&(y1 + dy * n))
+            })
+            .count() as i64;
+        let steps = rng.range(0, length - 1);
+
+        (x1, y1, x1 + dx * steps, y1 + dy * steps)
+    }
+
+    /// Marks every point of the lines on a grid and counts the cells covered twice.
+    fn naive_overlaps(lines: &[Line], diagonals: bool) -> usize {
+        let mut grid = [[0; SIZE as usize]; SIZE as usize];
+
+        for &(x1, y1, x2, y2) in lines {
+            if !diagonals && x1 != x2 && y1 != y2 {
+                continue;
+            }
+
+            let (dx, dy) = ((x2 - x1).signum(), (y2 - y1).signum());
+
+            for n in 0..=(x2 - x1).abs().max((y2 - y1).abs()) {
+                grid[(y1 + dy * n) as usize][(x1 + dx * n) as usize] += 1;
+            }
+        }
+
+        grid.iter().flatten().filter(|count| **count > 1).count()
+    }
+
+    #[test]
+    fn overlaps_match_grid_marking() {
+        check(
+            5,
+            |rng| (0..rng.range(1, 8)).map(|_| line(rng)).collect::<Vec<_>>(),
+            |lines| shrink_vec(lines, 1, no_shrink),
+            |lines| {
+                let input = lines
+                    .iter()
+                    .map(|(x1, y1, x2, y2)| format!("{},{} -> {},{}", x1, y1, x2, y2))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let segments = parse_input(&input).unwrap();
+                let (straight, all) = (naive_overlaps(lines, false), naive_overlaps(lines, true));
+
+                part1(&segments) == straight
+                    && part1_all_points(&segments) == straight
+                    && part2(&segments) == all
+                    && part2_all_points(&segments) == all
+            },
+        );
     }
 }
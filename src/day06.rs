@@ -1,8 +1,22 @@
+use crate::error::{parse_number, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 
+const DAY: u8 = 6;
+const MAX_TIMER: u8 = 8;
+
 #[aoc_generator(day6)]
-fn parse_input(input: &str) -> Vec<u8> {
-    input.split(',').map(|c| c.parse().unwrap()).collect()
+fn parse_input(input: &str) -> Result<Vec<u8>, ParseError> {
+    let input: &str = &normalize(input);
+
+    input
+        .split(',')
+        .map(|c| match parse_number(DAY, input, c)? {
+            timer if timer <= MAX_TIMER => Ok(timer),
+            _ => Err(ParseError::new(DAY, input, c, "timer out of range")),
+        })
+        .collect()
 }
 
 fn count_fish_by_day(fish_cycles: &[u8]) -> [u64; 9] {
@@ -46,6 +60,29 @@ fn part2(initial_cycles: &[u8]) -> u64 {
     fish_by_day.iter().sum()
 }
 
+/// Day 6: Lanternfish.
+pub struct Day06;
+
+impl Solver for Day06 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<u8>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,11 +91,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 5_934);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 5_934);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 26_984_457_539);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 26_984_457_539);
     }
 }
@@ -1,9 +1,19 @@
+use crate::error::{parse_number, ParseError};
+use crate::solver::{Solver, Variant};
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::ops::Sub;
 
+const DAY: u8 = 7;
+
 #[aoc_generator(day7)]
-fn parse_input(input: &str) -> Vec<i32> {
-    input.split(',').map(|c| c.parse().unwrap()).collect()
+fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    let input: &str = &normalize(input);
+
+    input
+        .split(',')
+        .map(|c| parse_number(DAY, input, c))
+        .collect()
 }
 
 #[aoc(day7, part1, median)]
@@ -65,6 +75,9 @@ fn part2(positions: &[i32]) -> i32 {
     min_cost(positions, geometric_cost)
 }
 
+/// Moving from `t` to `t + 1` changes the cost by `n * t - sum + count(x <= t)`, which
+/// is negative below `floor(mean)` and never negative from `ceil(mean)` on, so one of
+/// the two is optimal.
 #[aoc(day7, part2, mean)]
 fn part2_mean(positions: &[i32]) -> i32 {
     let mean = positions.iter().sum::<i32>() as f64 / positions.len() as f64;
@@ -74,29 +87,99 @@ fn part2_mean(positions: &[i32]) -> i32 {
     geometric_cost(positions, mean_floor).min(geometric_cost(positions, mean_ceil))
 }
 
+/// Day 7: The Treachery of Whales.
+pub struct Day07;
+
+impl Solver for Day07 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+
+    fn part1_variants() -> Vec<Variant<Self::Input, Self::Part1>> {
+        vec![("median", |positions| part1_median(positions))]
+    }
+
+    fn part2_variants() -> Vec<Variant<Self::Input, Self::Part2>> {
+        vec![("mean", |positions| part2_mean(positions))]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::property::{check, shrink_int, shrink_vec};
 
     static TEST_INPUT: &str = r"16,1,2,0,4,2,7,1,2,14";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 37);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 37);
     }
 
     #[test]
     fn part1_median_example() {
-        assert_eq!(part1_median(&parse_input(TEST_INPUT)), 37);
+        assert_eq!(part1_median(&parse_input(TEST_INPUT).unwrap()), 37);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 168);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 168);
     }
 
     #[test]
     fn part2_mean_example() {
-        assert_eq!(part2_mean(&parse_input(TEST_INPUT)), 168);
+        assert_eq!(part2_mean(&parse_input(TEST_INPUT).unwrap()), 168);
+    }
+
+    /// Crabs spread over a range, or mostly stacked on one position with a few
+    /// outliers, which pulls the mean furthest from the optimal position.
+    fn positions(rng: &mut crate::inputs::Rng) -> Vec<i32> {
+        let (stacked, spread) = if rng.ratio(1, 2) {
+            (0, rng.range(1, 20))
+        } else {
+            (rng.range(1, 20), rng.range(1, 3))
+        };
+        let position = rng.range(0, 1000);
+
+        let mut positions = (0..stacked)
+            .map(|_| position as i32)
+            .chain((0..spread).map(|_| rng.range(0, 1000) as i32))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut positions);
+        positions
+    }
+
+    #[test]
+    fn median_matches_search() {
+        check(
+            7,
+            positions,
+            |positions| shrink_vec(positions, 1, shrink_int),
+            |positions| part1_median(positions) == part1(positions),
+        );
+    }
+
+    #[test]
+    fn mean_matches_search() {
+        check(
+            7,
+            positions,
+            |positions| shrink_vec(positions, 1, shrink_int),
+            |positions| part2_mean(positions) == part2(positions),
+        );
     }
 }
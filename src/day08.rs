@@ -1,26 +1,65 @@
+use crate::error::{char_at, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashSet;
 
-type Entry = (Vec<HashSet<char>>, Vec<HashSet<char>>);
+const DAY: u8 = 8;
+const UNIQUE_PATTERNS_COUNT: usize = 10;
+const OUTPUT_DIGITS_COUNT: usize = 4;
+
+pub type Entry = (Vec<HashSet<char>>, Vec<HashSet<char>>);
+
+fn parse_patterns(
+    input: &str,
+    patterns_str: &str,
+    count: usize,
+) -> Result<Vec<HashSet<char>>, ParseError> {
+    let patterns = patterns_str
+        .split_whitespace()
+        .map(
+            |pattern| match pattern.find(|c| !('a'..='g').contains(&c)) {
+                Some(i) => Err(ParseError::new(
+                    DAY,
+                    input,
+                    char_at(pattern, i),
+                    "invalid segment",
+                )),
+                None => Ok(pattern.chars().collect()),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if patterns.len() != count {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            patterns_str,
+            format!("expected {} patterns", count),
+        ));
+    }
+
+    Ok(patterns)
+}
 
 #[aoc_generator(day8)]
-fn parse_input(input: &str) -> Vec<Entry> {
+fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let input: &str = &normalize(input);
+
     input
         .lines()
         .map(|l| {
             let mut iter = l.split('|');
-            (
-                iter.next()
-                    .unwrap()
-                    .split_whitespace()
-                    .map(|s| s.chars().collect())
-                    .collect::<Vec<_>>(),
-                iter.next()
-                    .unwrap()
-                    .split_whitespace()
-                    .map(|s| s.chars().collect())
-                    .collect::<Vec<_>>(),
-            )
+            let unique_signal_patterns = iter
+                .next()
+                .ok_or_else(|| ParseError::new(DAY, input, l, "missing signal patterns"))
+                .and_then(|p| parse_patterns(input, p, UNIQUE_PATTERNS_COUNT))?;
+            let four_digit_output_value = iter
+                .next()
+                .ok_or_else(|| ParseError::new(DAY, input, l, "missing output value"))
+                .and_then(|p| parse_patterns(input, p, OUTPUT_DIGITS_COUNT))?;
+
+            Ok((unique_signal_patterns, four_digit_output_value))
         })
         .collect()
 }
@@ -180,6 +219,29 @@ fn part2(entries: &[Entry]) -> u32 {
     result
 }
 
+/// Day 8: Seven Segment Search.
+pub struct Day08;
+
+impl Solver for Day08 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,11 +259,11 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 26);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 26);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 61_229);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 61_229);
     }
 }
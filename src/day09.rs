@@ -1,110 +1,83 @@
+use crate::error::ParseError;
+use crate::grid::{Grid, Position};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashSet, VecDeque};
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-struct Vec2 {
-    x: i32,
-    y: i32,
-}
-
-type Point = Vec2;
+const DAY: u8 = 9;
 
-struct DepthMap(HashMap<Point, i32>);
+pub struct DepthMap(Grid<i32>);
 
 impl DepthMap {
-    fn neighbor_heights(&self, point: &Point) -> Vec<(Point, i32)> {
-        [(-1, 0), (1, 0), (0, -1), (0, 1)]
-            .into_iter()
-            .map(|(x, y)| Point {
-                x: point.x + x,
-                y: point.y + y,
-            })
-            .filter_map(|p| self.0.get_key_value(&p))
-            .map(|(p, h)| (p.to_owned(), h.to_owned()))
-            .collect()
+    fn neighbor_heights(&self, point: Position) -> impl Iterator<Item = (Position, i32)> + '_ {
+        self.0.neighbors4(point).map(|p| (p, self.0[p]))
     }
 
-    fn is_low_point(&self, point: &Point) -> bool {
-        if let Some(height) = self.0.get(point) {
-            return self
-                .neighbor_heights(point)
-                .iter()
-                .all(|(_, neighbor_height)| *neighbor_height > *height);
-        }
+    fn is_low_point(&self, point: Position) -> bool {
+        let height = self.0[point];
 
-        false
+        self.neighbor_heights(point)
+            .all(|(_, neighbor_height)| neighbor_height > height)
     }
 
-    fn risk_level(&self, point: &Point) -> i32 {
+    fn risk_level(&self, point: Position) -> i32 {
         if self.is_low_point(point) {
-            self.0.get(point).unwrap() + 1
+            self.0[point] + 1
         } else {
             0
         }
     }
 
-    fn basin_size(&self, point: &Point) -> usize {
-        if let Some(height) = self.0.get(point) {
-            if *height == 9 {
-                return 0;
-            }
+    fn basin_size(&self, point: Position) -> usize {
+        let height = self.0[point];
 
-            let mut queue = VecDeque::from(vec![(point.to_owned(), *height)]);
-            let mut visited: HashSet<Point> = [point.to_owned()].iter().cloned().collect();
-
-            while !queue.is_empty() {
-                let (current_point, current_height) = queue.pop_front().unwrap();
-
-                for (neighbor_point, neighbor_height) in self.neighbor_heights(&current_point) {
-                    if neighbor_height > current_height
-                        && neighbor_height != 9
-                        && !visited.contains(&neighbor_point)
-                    {
-                        queue.push_back((neighbor_point.to_owned(), neighbor_height));
-                        visited.insert(neighbor_point);
-                    };
-                }
-            }
+        if height == 9 {
+            return 0;
+        }
 
-            return visited.len();
+        let mut queue = VecDeque::from(vec![(point, height)]);
+        let mut visited: HashSet<Position> = [point].into_iter().collect();
+
+        while let Some((current_point, current_height)) = queue.pop_front() {
+            for (neighbor_point, neighbor_height) in self.neighbor_heights(current_point) {
+                if neighbor_height > current_height
+                    && neighbor_height != 9
+                    && !visited.contains(&neighbor_point)
+                {
+                    queue.push_back((neighbor_point, neighbor_height));
+                    visited.insert(neighbor_point);
+                };
+            }
         }
 
-        0
+        visited.len()
     }
 }
 
 #[aoc_generator(day9)]
-fn parse_input(input: &str) -> DepthMap {
-    DepthMap(
-        input
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars().enumerate().map(move |(x, c)| {
-                    (
-                        Point {
-                            x: x as i32,
-                            y: y as i32,
-                        },
-                        c.to_digit(10).unwrap() as i32,
-                    )
-                })
-            })
-            .collect(),
-    )
+fn parse_input(input: &str) -> Result<DepthMap, ParseError> {
+    let input: &str = &normalize(input);
+
+    Ok(DepthMap(Grid::parse(
+        DAY,
+        input,
+        |c| c.to_digit(10).map(|height| height as i32),
+        "invalid digit",
+    )?))
 }
 
 #[aoc(day9, part1)]
 fn part1(map: &DepthMap) -> i32 {
-    map.0.keys().map(|point| map.risk_level(point)).sum()
+    map.0.positions().map(|point| map.risk_level(point)).sum()
 }
 
 #[aoc(day9, part2)]
 fn part2(map: &DepthMap) -> usize {
     let mut basin_sizes = map
         .0
-        .keys()
-        .filter(|point| map.is_low_point(point))
+        .positions()
+        .filter(|point| map.is_low_point(*point))
         .map(|point| map.basin_size(point))
         .collect::<Vec<_>>();
 
@@ -113,6 +86,29 @@ fn part2(map: &DepthMap) -> usize {
     basin_sizes.into_iter().rev().take(3).product()
 }
 
+/// Day 9: Smoke Basin.
+pub struct Day09;
+
+impl Solver for Day09 {
+    const DAY: u8 = DAY;
+
+    type Input = DepthMap;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,11 +121,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 15);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 15);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 1134);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 1134);
     }
 }
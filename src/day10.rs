@@ -1,8 +1,28 @@
+use crate::error::{char_at, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 
+const DAY: u8 = 10;
+
 #[aoc_generator(day10)]
-fn parse_input(input: &str) -> Vec<String> {
-    input.lines().map(|l| l.to_owned()).collect()
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let input: &str = &normalize(input);
+
+    input
+        .lines()
+        .map(|l| {
+            match l.find(|c| matching_closing_bracket(c).is_none() && !is_closing_bracket(c)) {
+                Some(i) => Err(ParseError::new(
+                    DAY,
+                    input,
+                    char_at(l, i),
+                    "invalid bracket",
+                )),
+                None => Ok(l.to_owned()),
+            }
+        })
+        .collect()
 }
 
 fn is_closing_bracket(c: char) -> bool {
@@ -72,7 +92,7 @@ fn part1(lines: &[String]) -> u64 {
 }
 
 #[aoc(day10, part2)]
-fn part2(lines: &[String]) -> u64 {
+fn part2(lines: &[String]) -> Option<u64> {
     let mut total_scores = Vec::new();
 
     'next_line: for line in lines {
@@ -102,7 +122,30 @@ fn part2(lines: &[String]) -> u64 {
 
     total_scores.sort_unstable();
 
-    total_scores[total_scores.len() / 2]
+    total_scores.get(total_scores.len() / 2).copied()
+}
+
+/// Day 10: Syntax Scoring.
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<String>;
+    type Part1 = u64;
+    type Part2 = Option<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -122,11 +165,25 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 26_397);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 26_397);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 288_957);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), Some(288_957));
+    }
+
+    #[test]
+    fn only_corrupted_lines() {
+        assert_eq!(
+            part2(
+                &parse_input(
+                    "(]
+{()()()>"
+                )
+                .unwrap()
+            ),
+            None
+        );
     }
 }
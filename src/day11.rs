@@ -1,66 +1,93 @@
+use crate::error::ParseError;
+use crate::frames::{Frames, Render, Rgb};
+use crate::grid::Grid;
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::{HashSet, VecDeque};
 
-const MAP_SIZE: usize = 10;
-const OCTOPUSES_COUNT: usize = MAP_SIZE * MAP_SIZE;
+const DAY: u8 = 11;
 const THRESHOLD_ENERGY: u8 = 9;
 const MIN_ENERGY: u8 = 0;
 
+/// The energy level of an octopus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Energy(u8);
+
 #[derive(Clone)]
-struct EnergyLevelsMap([[u8; MAP_SIZE]; MAP_SIZE]);
+pub struct EnergyLevelsMap(Grid<Energy>);
 
 #[aoc_generator(day11)]
-fn parse_input(input: &str) -> EnergyLevelsMap {
-    let mut map = [[0; MAP_SIZE]; MAP_SIZE];
+fn parse_input(input: &str) -> Result<EnergyLevelsMap, ParseError> {
+    let input: &str = &normalize(input);
+
+    Ok(EnergyLevelsMap(Grid::parse(
+        DAY,
+        input,
+        |c| c.to_digit(10).map(|energy| Energy(energy as u8)),
+        "invalid digit",
+    )?))
+}
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            map[x][y] = c.to_digit(10).unwrap() as u8;
-        }
+impl Render for Energy {
+    fn glyph(&self) -> char {
+        char::from_digit(self.0 as u32, 10).unwrap_or('+')
     }
 
-    EnergyLevelsMap(map)
+    fn color(&self) -> Rgb {
+        match self.0 {
+            MIN_ENERGY => [255, 255, 192],
+            energy => {
+                let level = energy.saturating_mul(16);
+                [0, level, level.saturating_add(64)]
+            }
+        }
+    }
 }
 
 impl EnergyLevelsMap {
-    fn flashes_after_step(&mut self) -> usize {
+    /// Snapshots of the octopuses before the first step and after each of `steps` steps.
+    pub fn record(&self, steps: usize) -> Frames {
+        let mut energy_levels = self.to_owned();
+        let mut frames = Frames::default();
+
+        frames.capture(&energy_levels.0);
+
+        for _ in 0..steps {
+            energy_levels.flashes_after_step();
+            frames.capture(&energy_levels.0);
+        }
+
+        frames
+    }
+
+    pub(crate) fn flashes_after_step(&mut self) -> usize {
         let mut queue = VecDeque::new();
         let mut flashed = HashSet::new();
 
-        for x in 0..MAP_SIZE {
-            for y in 0..MAP_SIZE {
-                self.0[x][y] += 1;
+        for position in self.0.positions() {
+            self.0[position].0 += 1;
 
-                if self.0[x][y] > THRESHOLD_ENERGY {
-                    self.0[x][y] = MIN_ENERGY;
+            if self.0[position].0 > THRESHOLD_ENERGY {
+                self.0[position] = Energy(MIN_ENERGY);
 
-                    queue.push_back((x, y));
-                    flashed.insert((x, y));
-                }
+                queue.push_back(position);
+                flashed.insert(position);
             }
         }
 
-        while !queue.is_empty() {
-            let (x, y) = queue.pop_front().unwrap();
-
-            for dx in -1i8..=1 {
-                for dy in -1i8..=1 {
-                    let (nx, ny) = (x as i8 + dx, y as i8 + dy);
+        while let Some(position) = queue.pop_front() {
+            let neighbors = self.0.neighbors8(position).collect::<Vec<_>>();
 
-                    if nx >= 0
-                        && nx < MAP_SIZE as i8
-                        && ny >= 0
-                        && ny < MAP_SIZE as i8
-                        && !flashed.contains(&(nx as usize, ny as usize))
-                    {
-                        self.0[nx as usize][ny as usize] += 1;
+            for neighbor in neighbors {
+                if !flashed.contains(&neighbor) {
+                    self.0[neighbor].0 += 1;
 
-                        if self.0[nx as usize][ny as usize] > THRESHOLD_ENERGY {
-                            self.0[nx as usize][ny as usize] = MIN_ENERGY;
+                    if self.0[neighbor].0 > THRESHOLD_ENERGY {
+                        self.0[neighbor] = Energy(MIN_ENERGY);
 
-                            queue.push_back((nx as usize, ny as usize));
-                            flashed.insert((nx as usize, ny as usize));
-                        }
+                        queue.push_back(neighbor);
+                        flashed.insert(neighbor);
                     }
                 }
             }
@@ -83,7 +110,7 @@ fn part2(energy_levels: &EnergyLevelsMap) -> usize {
     let mut step = 1;
 
     loop {
-        if energy_levels.flashes_after_step() == OCTOPUSES_COUNT {
+        if energy_levels.flashes_after_step() == energy_levels.0.len() {
             return step;
         }
 
@@ -91,6 +118,29 @@ fn part2(energy_levels: &EnergyLevelsMap) -> usize {
     }
 }
 
+/// Day 11: Dumbo Octopus.
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u8 = DAY;
+
+    type Input = EnergyLevelsMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,11 +158,21 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 1_656);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 1_656);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 195);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 195);
+    }
+
+    #[test]
+    fn render_energy() {
+        assert_eq!(Energy(0).color(), [255, 255, 192]);
+        assert_eq!((Energy(9).glyph(), Energy(9).color()), ('9', [0, 144, 208]));
+        assert_eq!(
+            (Energy(20).glyph(), Energy(20).color()),
+            ('+', [0, 255, 255])
+        );
     }
 }
@@ -1,33 +1,48 @@
+use crate::error::{next_part, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::{HashMap, HashSet};
 
-type CaveSystem = HashMap<String, HashSet<String>>;
+const DAY: u8 = 12;
+
+pub type CaveSystem = HashMap<String, HashSet<String>>;
 
 const START: &str = "start";
 const END: &str = "end";
 
 #[aoc_generator(day12)]
-fn parse_input(input: &str) -> CaveSystem {
+fn parse_input(input: &str) -> Result<CaveSystem, ParseError> {
+    let input: &str = &normalize(input);
+
     let mut cave_system = CaveSystem::new();
 
     for line in input.lines() {
         let mut split = line.split('-');
 
-        let cave_a = split.next().unwrap();
-        let cave_b = split.next().unwrap();
+        let cave_a = next_part(DAY, line, &mut split, "cave")
+            .and_then(|cave| validate_cave(line, cave))
+            .map_err(|e| e.within(input, line))?;
+        let cave_b = next_part(DAY, line, &mut split, "connected cave")
+            .and_then(|cave| validate_cave(line, cave))
+            .map_err(|e| e.within(input, line))?;
 
-        let cave_a_connections = cave_system
-            .entry(cave_a.to_owned())
-            .or_insert_with(HashSet::new);
+        let cave_a_connections = cave_system.entry(cave_a.to_owned()).or_default();
         cave_a_connections.insert(cave_b.to_owned());
 
-        let cave_b_connections = cave_system
-            .entry(cave_b.to_owned())
-            .or_insert_with(HashSet::new);
+        let cave_b_connections = cave_system.entry(cave_b.to_owned()).or_default();
         cave_b_connections.insert(cave_a.to_owned());
     }
 
-    cave_system
+    Ok(cave_system)
+}
+
+fn validate_cave<'a>(line: &str, cave: &'a str) -> Result<&'a str, ParseError> {
+    if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+        Err(ParseError::new(DAY, line, cave, "invalid cave name"))
+    } else {
+        Ok(cave)
+    }
 }
 
 fn is_small(cave: &str) -> bool {
@@ -136,6 +151,29 @@ fn part2(cave_system: &CaveSystem) -> usize {
     )
 }
 
+/// Day 12: Passage Pathing.
+pub struct Day12;
+
+impl Solver for Day12 {
+    const DAY: u8 = DAY;
+
+    type Input = CaveSystem;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,15 +218,15 @@ start-RW";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT_1)), 10);
-        assert_eq!(part1(&parse_input(TEST_INPUT_2)), 19);
-        assert_eq!(part1(&parse_input(TEST_INPUT_3)), 226);
+        assert_eq!(part1(&parse_input(TEST_INPUT_1).unwrap()), 10);
+        assert_eq!(part1(&parse_input(TEST_INPUT_2).unwrap()), 19);
+        assert_eq!(part1(&parse_input(TEST_INPUT_3).unwrap()), 226);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT_1)), 36);
-        assert_eq!(part2(&parse_input(TEST_INPUT_2)), 103);
-        assert_eq!(part2(&parse_input(TEST_INPUT_3)), 3_509);
+        assert_eq!(part2(&parse_input(TEST_INPUT_1).unwrap()), 36);
+        assert_eq!(part2(&parse_input(TEST_INPUT_2).unwrap()), 103);
+        assert_eq!(part2(&parse_input(TEST_INPUT_3).unwrap()), 3_509);
     }
 }
@@ -1,54 +1,71 @@
 use crate::day13::Instruction::*;
+use crate::error::{next_part, parse_number, ParseError};
+use crate::geometry::Vec2;
+use crate::json::Json;
+use crate::solver::Solver;
+use crate::text::{normalize, paragraphs};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashSet;
-use std::hash::Hash;
 
+const DAY: u8 = 13;
 const INSTRUCTION_PREFIX: &str = "fold along ";
 
-enum Instruction {
+pub enum Instruction {
     FoldAlongX(i32),
     FoldAlongY(i32),
 }
 
-impl From<&str> for Instruction {
-    fn from(instruction: &str) -> Self {
-        let value = instruction[INSTRUCTION_PREFIX.len() + 2..].parse().unwrap();
-
-        match instruction.chars().nth(INSTRUCTION_PREFIX.len()).unwrap() {
-            'x' => FoldAlongX(value),
-            'y' => FoldAlongY(value),
-            _ => unreachable!(),
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(instruction: &str) -> Result<Self, Self::Error> {
+        let fold = instruction
+            .strip_prefix(INSTRUCTION_PREFIX)
+            .ok_or_else(|| ParseError::new(DAY, instruction, instruction, "unknown instruction"))?;
+        let mut split = fold.split('=');
+
+        let axis = next_part(DAY, instruction, &mut split, "axis")?;
+        let value = parse_number(
+            DAY,
+            instruction,
+            next_part(DAY, instruction, &mut split, "value")?,
+        )?;
+
+        match axis {
+            "x" => Ok(FoldAlongX(value)),
+            "y" => Ok(FoldAlongY(value)),
+            _ => Err(ParseError::new(DAY, instruction, axis, "unknown axis")),
         }
     }
 }
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-struct Vec2 {
-    x: i32,
-    y: i32,
-}
-
-type Point = Vec2;
-
-impl From<&str> for Vec2 {
-    fn from(vec2: &str) -> Self {
-        let mut iter = vec2.split(',');
-
-        Vec2 {
-            x: iter.next().unwrap().parse().unwrap(),
-            y: iter.next().unwrap().parse().unwrap(),
-        }
-    }
-}
+pub type Point = Vec2<i32>;
 
 #[aoc_generator(day13)]
-fn parse_input(input: &str) -> (HashSet<Point>, Vec<Instruction>) {
-    let mut split = input.split("\n\n");
+fn parse_input(input: &str) -> Result<(HashSet<Point>, Vec<Instruction>), ParseError> {
+    let input: &str = &normalize(input);
+
+    let mut split = paragraphs(input);
+
+    let points = next_part(DAY, input, &mut split, "dots")?
+        .lines()
+        .map(|l| Point::from_csv(DAY, l).map_err(|e| e.within(input, l)))
+        .collect::<Result<_, _>>()?;
+    let instructions = next_part(DAY, input, &mut split, "fold instructions")?
+        .lines()
+        .map(|l| Instruction::try_from(l).map_err(|e| e.within(input, l)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if instructions.is_empty() {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            input,
+            "missing fold instructions",
+        ));
+    }
 
-    (
-        split.next().unwrap().lines().map(|l| l.into()).collect(),
-        split.next().unwrap().lines().map(|l| l.into()).collect(),
-    )
+    Ok((points, instructions))
 }
 
 fn fold_along_axis(points: &HashSet<Point>, instruction: &Instruction) -> HashSet<Point> {
@@ -96,6 +113,35 @@ fn part2((points, instructions): &(HashSet<Point>, Vec<Instruction>)) -> String
     result
 }
 
+/// Day 13: Transparent Origami.
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u8 = DAY;
+
+    type Input = (HashSet<Point>, Vec<Instruction>);
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+
+    fn details(input: &Self::Input) -> Vec<(&'static str, Json)> {
+        let code = part2(input).lines().skip(1).map(|row| row.into()).collect();
+
+        vec![("code", Json::Array(code))]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,13 +170,13 @@ fold along x=5";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 17);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 17);
     }
 
     #[test]
     fn part2_example() {
         assert_eq!(
-            part2(&parse_input(TEST_INPUT)),
+            part2(&parse_input(TEST_INPUT).unwrap()),
             r"
 #####
 #...#
@@ -1,28 +1,65 @@
+use crate::error::{next_part, ParseError};
+use crate::solver::Solver;
+use crate::text::{normalize, paragraphs};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashMap;
 
-type PolymerTemplate = Vec<char>;
-type Element = char;
+const DAY: u8 = 14;
+
+pub type PolymerTemplate = Vec<char>;
+pub type Element = char;
+pub type PairInsertionRules = HashMap<(Element, Element), Element>;
+
+fn parse_rule(rule: &str) -> Result<((Element, Element), Element), ParseError> {
+    let mut split = rule.split(" -> ");
+
+    let pair = next_part(DAY, rule, &mut split, "pair")?;
+    let inserted = next_part(DAY, rule, &mut split, "inserted element")?;
+
+    match (
+        &pair.chars().collect::<Vec<_>>()[..],
+        &inserted.chars().collect::<Vec<_>>()[..],
+    ) {
+        (&[first, second], &[element]) => Ok(((first, second), element)),
+        ([_, _], _) => Err(ParseError::new(
+            DAY,
+            rule,
+            inserted,
+            "expected a single element",
+        )),
+        _ => Err(ParseError::new(
+            DAY,
+            rule,
+            pair,
+            "expected a pair of elements",
+        )),
+    }
+}
 
 #[aoc_generator(day14)]
-fn parse_input(input: &str) -> (PolymerTemplate, HashMap<(Element, Element), Element>) {
-    let mut split = input.split("\n\n");
-
-    (
-        split.next().unwrap().chars().collect(),
-        split
-            .next()
-            .unwrap()
+fn parse_input(input: &str) -> Result<(PolymerTemplate, PairInsertionRules), ParseError> {
+    let input: &str = &normalize(input);
+
+    let mut split = paragraphs(input);
+
+    let polymer_template = next_part(DAY, input, &mut split, "polymer template")?;
+
+    if polymer_template.is_empty() {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            polymer_template,
+            "empty polymer template",
+        ));
+    }
+
+    Ok((
+        polymer_template.chars().collect(),
+        next_part(DAY, input, &mut split, "pair insertion rules")?
             .lines()
-            .map(|l| {
-                let mut chars = l.chars();
-                (
-                    (chars.next().unwrap(), chars.next().unwrap()),
-                    chars.last().unwrap(),
-                )
-            })
-            .collect(),
-    )
+            .map(|l| parse_rule(l).map_err(|e| e.within(input, l)))
+            .collect::<Result<_, _>>()?,
+    ))
 }
 
 fn count_elements_and_pairs(
@@ -53,7 +90,7 @@ fn count_elements_and_pairs(
 fn make_insertion_step(
     pair_counts: &mut HashMap<(Element, Element), usize>,
     element_counts: &mut HashMap<Element, usize>,
-    pair_insertion_rules: &HashMap<(Element, Element), Element>,
+    pair_insertion_rules: &PairInsertionRules,
 ) {
     let mut new_pair_counts = pair_counts.clone();
 
@@ -84,7 +121,7 @@ fn make_insertion_step(
 
 fn make_n_steps(
     polymer_template: &[Element],
-    pair_insertion_rules: &HashMap<(Element, Element), Element>,
+    pair_insertion_rules: &PairInsertionRules,
     steps: usize,
 ) -> usize {
     let (mut element_counts, mut pair_counts) = count_elements_and_pairs(polymer_template);
@@ -98,27 +135,46 @@ fn make_n_steps(
 
 #[aoc(day14, part1)]
 fn part1(
-    (polymer_template, pair_insertion_rules): &(
-        PolymerTemplate,
-        HashMap<(Element, Element), Element>,
-    ),
+    (polymer_template, pair_insertion_rules): &(PolymerTemplate, PairInsertionRules),
 ) -> usize {
     make_n_steps(polymer_template, pair_insertion_rules, 10)
 }
 
 #[aoc(day14, part2)]
 fn part2(
-    (polymer_template, pair_insertion_rules): &(
-        PolymerTemplate,
-        HashMap<(Element, Element), Element>,
-    ),
+    (polymer_template, pair_insertion_rules): &(PolymerTemplate, PairInsertionRules),
 ) -> usize {
     make_n_steps(polymer_template, pair_insertion_rules, 40)
 }
 
+/// Day 14: Extended Polymerization.
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u8 = DAY;
+
+    type Input = (PolymerTemplate, PairInsertionRules);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::Rng;
+    use crate::property::{check, no_shrink, shrink_vec};
 
     static TEST_INPUT: &str = r"NNCB
 
@@ -141,11 +197,84 @@ CN -> C";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 1_588);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 1_588);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 2_188_189_693_529);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 2_188_189_693_529);
+    }
+
+    const ELEMENTS: [Element; 4] = ['A', 'B', 'C', 'D'];
+
+    type Case = (PolymerTemplate, Vec<((Element, Element), Element)>, usize);
+
+    fn expand(polymer: &[Element], rules: &PairInsertionRules) -> PolymerTemplate {
+        let mut expanded = vec![polymer[0]];
+
+        for pair in polymer.windows(2) {
+            expanded.extend(rules.get(&(pair[0], pair[1])));
+            expanded.push(pair[1]);
+        }
+
+        expanded
+    }
+
+    fn naive_n_steps(polymer: &[Element], rules: &PairInsertionRules, steps: usize) -> usize {
+        let mut polymer = polymer.to_vec();
+
+        for _ in 0..steps {
+            polymer = expand(&polymer, rules);
+        }
+
+        let counts = ELEMENTS
+            .iter()
+            .map(|e| polymer.iter().filter(|p| *p == e).count())
+            .filter(|count| *count > 0)
+            .collect::<Vec<_>>();
+
+        counts.iter().max().unwrap() - counts.iter().min().unwrap()
+    }
+
+    fn case(rng: &mut Rng) -> Case {
+        let template = (0..rng.range(1, 6))
+            .map(|_| *rng.choose(&ELEMENTS))
+            .collect();
+        let mut rules = ELEMENTS
+            .iter()
+            .flat_map(|a| ELEMENTS.iter().map(move |b| (*a, *b)))
+            .map(|pair| (pair, *rng.choose(&ELEMENTS)))
+            .collect::<Vec<_>>();
+        rules.retain(|_| rng.ratio(3, 4));
+
+        (template, rules, rng.range(0, 8) as usize)
+    }
+
+    fn shrink_case((template, rules, steps): &Case) -> Vec<Case> {
+        let mut candidates = (0..*steps)
+            .rev()
+            .map(|fewer| (template.clone(), rules.clone(), fewer))
+            .collect::<Vec<_>>();
+
+        candidates.extend(
+            shrink_vec(template, 1, no_shrink)
+                .into_iter()
+                .map(|template| (template, rules.clone(), *steps)),
+        );
+        candidates.extend(
+            shrink_vec(rules, 0, no_shrink)
+                .into_iter()
+                .map(|rules| (template.clone(), rules, *steps)),
+        );
+
+        candidates
+    }
+
+    #[test]
+    fn pair_counting_matches_expansion() {
+        check(14, case, shrink_case, |(template, rules, steps)| {
+            let rules = rules.iter().copied().collect();
+            make_n_steps(template, &rules, *steps) == naive_n_steps(template, &rules, *steps)
+        });
     }
 }
@@ -1,72 +1,52 @@
+use crate::error::ParseError;
+use crate::grid::{Grid, Position};
+use crate::json::Json;
+use crate::search::{a_star, Path};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
-struct Vec2 {
-    x: i32,
-    y: i32,
-}
+const DAY: u8 = 15;
+
+type Point = Position;
 
-type Point = Vec2;
+const TOP_LEFT: Point = Point::new(0, 0);
 
 #[derive(Debug)]
-struct RiskLevelMap {
-    map: HashMap<Point, usize>,
-    width: usize,
-    height: usize,
+pub struct RiskLevelMap {
+    map: Grid<usize>,
 }
 
-impl From<&str> for RiskLevelMap {
-    fn from(map_str: &str) -> Self {
-        RiskLevelMap {
-            map: map_str
-                .lines()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars().enumerate().map(move |(x, c)| {
-                        (
-                            Point {
-                                x: x as i32,
-                                y: y as i32,
-                            },
-                            c.to_digit(10).unwrap() as usize,
-                        )
-                    })
-                })
-                .collect(),
-            width: map_str.lines().next().unwrap().chars().count(),
-            height: map_str.lines().count(),
-        }
+impl TryFrom<&str> for RiskLevelMap {
+    type Error = ParseError;
+
+    fn try_from(map_str: &str) -> Result<Self, Self::Error> {
+        Ok(RiskLevelMap {
+            map: Grid::parse(
+                DAY,
+                map_str,
+                |c| {
+                    c.to_digit(10)
+                        .filter(|risk| *risk > 0)
+                        .map(|risk| risk as usize)
+                },
+                "invalid risk level",
+            )?,
+        })
     }
 }
 
 impl RiskLevelMap {
     fn neighbor_risks(&self, point: &Point, large_map_multiplier: usize) -> Vec<(Point, usize)> {
-        [(-1, 0), (1, 0), (0, -1), (0, 1)]
-            .into_iter()
-            .map(|(x, y)| Point {
-                x: point.x + x,
-                y: point.y + y,
-            })
-            .filter(|p| {
-                p.x >= 0
-                    && p.y >= 0
-                    && p.x < (self.width * large_map_multiplier) as i32
-                    && p.y < (self.height * large_map_multiplier) as i32
-            })
+        let (width, height) = (self.map.width(), self.map.height());
+
+        point
+            .neighbors4()
+            .filter(|p| p.x < width * large_map_multiplier && p.y < height * large_map_multiplier)
             .map(|p| {
                 (
                     p,
-                    (self
-                        .map
-                        .get(&Point {
-                            x: p.x % self.width as i32,
-                            y: p.y % self.height as i32,
-                        })
-                        .unwrap()
-                        + p.x as usize / self.width
-                        + p.y as usize / self.height
+                    (self.map[Point::new(p.x % width, p.y % height)] + p.x / width + p.y / height
                         - 1)
                         % 9
                         + 1,
@@ -75,90 +55,67 @@ impl RiskLevelMap {
             .collect()
     }
 
-    fn dijkstra_diagonal(&self, large_map_multiplier: usize) -> Option<usize> {
-        const TOP_LEFT: Point = Point { x: 0, y: 0 };
-
-        let mut dist: HashMap<Point, usize> = HashMap::new();
-        let mut heap = BinaryHeap::new();
-
-        dist.insert(TOP_LEFT, 0);
-
-        heap.push(State {
-            cost: 0,
-            position: TOP_LEFT,
-        });
-
-        while let Some(State { cost, position }) = heap.pop() {
-            if position.x == (self.width * large_map_multiplier - 1) as i32
-                && position.y == (self.height * large_map_multiplier - 1) as i32
-            {
-                return Some(cost);
-            }
-
-            if let Some(old_cost) = dist.get(&position) {
-                if cost > *old_cost {
-                    continue;
-                }
-            }
-
-            for (point, risk) in self.neighbor_risks(&position, large_map_multiplier) {
-                let next = State {
-                    cost: cost + risk,
-                    position: point,
-                };
-
-                if next.cost < *dist.get(&next.position).unwrap_or(&(next.cost + 1)) {
-                    heap.push(next);
-                    dist.insert(next.position, next.cost);
-                }
-            }
-        }
-
-        None
-    }
-}
-
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    position: Point,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| {
-                (self.position.y + self.position.x).cmp(&(other.position.x + other.position.y))
-            })
-            .then_with(|| {
-                (other.position.y - other.position.x)
-                    .abs()
-                    .cmp(&(self.position.x - self.position.y).abs())
-            })
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Path with the lowest total risk from the top left to the bottom right corner
+    /// of the map tiled `large_map_multiplier` times in each direction.
+    pub fn lowest_risk_path(&self, large_map_multiplier: usize) -> Option<Path<Point>> {
+        let bottom_right = Point::new(
+            self.map.width() * large_map_multiplier - 1,
+            self.map.height() * large_map_multiplier - 1,
+        );
+
+        a_star(
+            TOP_LEFT,
+            |position| self.neighbor_risks(position, large_map_multiplier),
+            |position| position.manhattan(bottom_right),
+            |position| *position == bottom_right,
+        )
     }
 }
 
 #[aoc_generator(day15)]
-fn parse_input(input: &str) -> RiskLevelMap {
-    input.into()
+fn parse_input(input: &str) -> Result<RiskLevelMap, ParseError> {
+    let input: &str = &normalize(input);
+
+    input.try_into()
 }
 
 #[aoc(day15, part1)]
 fn part1(risk_map: &RiskLevelMap) -> Option<usize> {
-    risk_map.dijkstra_diagonal(1)
+    risk_map.lowest_risk_path(1).map(|path| path.cost)
 }
 
 #[aoc(day15, part2)]
 fn part2(risk_map: &RiskLevelMap) -> Option<usize> {
-    risk_map.dijkstra_diagonal(5)
+    risk_map.lowest_risk_path(5).map(|path| path.cost)
+}
+
+/// Day 15: Chiton.
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u8 = DAY;
+
+    type Input = RiskLevelMap;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+
+    fn details(input: &Self::Input) -> Vec<(&'static str, Json)> {
+        let path = |multiplier| input.lowest_risk_path(multiplier).map(|path| path.states);
+
+        vec![("path", path(1).into()), ("large_map_path", path(5).into())]
+    }
 }
 
 #[cfg(test)]
@@ -178,11 +135,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), Some(40));
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), Some(40));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), Some(315));
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), Some(315));
     }
 }
@@ -1,7 +1,11 @@
 use crate::day16::PacketBody::*;
 use crate::day16::Token::*;
+use crate::error::{char_at, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 
+const DAY: u8 = 16;
 const LITERAL_VALUE_TYPE_ID: u64 = 4;
 const VERSION_LEN: usize = 3;
 const TYPE_ID_LEN: usize = 3;
@@ -10,7 +14,7 @@ const TOTAL_BIT_LEN_LEN: usize = 15;
 const SUB_PACKETS_NUM_LEN: usize = 11;
 
 #[derive(Clone, Copy, Debug)]
-enum Token {
+pub enum Token {
     LiteralValue(u64),
     Sum,
     Product,
@@ -23,9 +27,9 @@ enum Token {
 }
 
 #[derive(Debug)]
-struct Packet {
-    version: u64,
-    packet_type: Token,
+pub struct Packet {
+    pub version: u64,
+    pub packet_type: Token,
 }
 
 #[derive(Eq, PartialEq)]
@@ -35,20 +39,68 @@ enum PacketBody {
 }
 
 #[aoc_generator(day16)]
-fn parse_input(input: &str) -> Vec<Packet> {
+fn parse_input(input: &str) -> Result<Vec<Packet>, ParseError> {
+    let input: &str = &normalize(input);
+
     let packet_string = input
-        .chars()
-        .map(|c| c.to_digit(16).unwrap())
-        .map(|d| format!("{:04b}", d))
-        .fold(String::new(), |acc, s| acc + s.as_str());
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(16)
+                .ok_or_else(|| ParseError::new(DAY, input, char_at(input, i), "invalid hex digit"))
+        })
+        .map(|d| d.map(|d| format!("{:04b}", d)))
+        .collect::<Result<String, _>>()?;
+
+    let error_at = |index: usize, message: &str| {
+        ParseError::new(
+            DAY,
+            input,
+            char_at(input, (index / 4).min(input.len())),
+            message,
+        )
+    };
+
+    let read_bits = |index: usize, len: usize| {
+        packet_string
+            .get(index..index + len)
+            .ok_or_else(|| error_at(index, "unexpected end of transmission"))
+    };
+
+    let consume_bits = |parsing_stack: &mut Vec<PacketBody>,
+                        packet_body: PacketBody,
+                        bits_used: usize,
+                        index: usize| {
+        for packet_body in parsing_stack.iter_mut() {
+            if let Bits(bits_num) = packet_body {
+                *packet_body = Bits(
+                    bits_num
+                        .checked_sub(bits_used)
+                        .ok_or_else(|| error_at(index, "sub-packets exceed declared length"))?,
+                );
+            }
+        }
+
+        match packet_body {
+            Bits(bits_num) => {
+                parsing_stack.push(Bits(
+                    bits_num
+                        .checked_sub(bits_used)
+                        .ok_or_else(|| error_at(index, "sub-packets exceed declared length"))?,
+                ));
+            }
+            SubPackets(sub_packets_num) => {
+                parsing_stack.push(SubPackets(sub_packets_num - 1));
+            }
+        }
+
+        Ok(())
+    };
 
     let mut index = 0;
     let mut parsing_stack = vec![SubPackets(1)];
     let mut packet_stack = Vec::new();
 
-    while !parsing_stack.is_empty() {
-        let packet_body = parsing_stack.pop().unwrap();
-
+    while let Some(packet_body) = parsing_stack.pop() {
         if packet_body == Bits(0) || packet_body == SubPackets(0) {
             packet_stack.push(Packet {
                 version: 0,
@@ -60,12 +112,12 @@ fn parse_input(input: &str) -> Vec<Packet> {
 
         let mut bits_used = 0;
 
-        let version = u64::from_str_radix(&packet_string[index..index + VERSION_LEN], 2).unwrap();
+        let version = u64::from_str_radix(read_bits(index, VERSION_LEN)?, 2).unwrap();
 
         index += VERSION_LEN;
         bits_used += VERSION_LEN;
 
-        let type_id = u64::from_str_radix(&packet_string[index..index + TYPE_ID_LEN], 2).unwrap();
+        let type_id = u64::from_str_radix(read_bits(index, TYPE_ID_LEN)?, 2).unwrap();
 
         index += TYPE_ID_LEN;
         bits_used += TYPE_ID_LEN;
@@ -74,42 +126,27 @@ fn parse_input(input: &str) -> Vec<Packet> {
             let mut value = String::new();
 
             loop {
-                let group_prefix = packet_string
-                    .chars()
-                    .nth(index)
-                    .unwrap()
-                    .to_digit(2)
-                    .unwrap();
+                let group = read_bits(index, GROUP_LEN)?;
 
-                value.push_str(&packet_string[index + 1..index + GROUP_LEN]);
+                value.push_str(&group[1..]);
 
                 index += GROUP_LEN;
                 bits_used += GROUP_LEN;
 
-                if group_prefix == 0 {
+                if group.starts_with('0') {
                     break;
                 }
             }
 
             packet_stack.push(Packet {
                 version,
-                packet_type: Token::LiteralValue(u64::from_str_radix(value.as_str(), 2).unwrap()),
+                packet_type: Token::LiteralValue(
+                    u64::from_str_radix(value.as_str(), 2)
+                        .map_err(|_| error_at(index, "literal value too large"))?,
+                ),
             });
 
-            for packet_body in parsing_stack.iter_mut() {
-                if let Bits(bits_num) = packet_body {
-                    *packet_body = Bits(*bits_num - bits_used);
-                }
-            }
-
-            match packet_body {
-                Bits(bits_num) => {
-                    parsing_stack.push(Bits(bits_num - bits_used));
-                }
-                SubPackets(sub_packets_num) => {
-                    parsing_stack.push(SubPackets(sub_packets_num - 1));
-                }
-            }
+            consume_bits(&mut parsing_stack, packet_body, bits_used, index)?;
         } else {
             packet_stack.push(Packet {
                 version,
@@ -125,53 +162,33 @@ fn parse_input(input: &str) -> Vec<Packet> {
                 },
             });
 
-            let length_type_id = packet_string
-                .chars()
-                .nth(index)
-                .unwrap()
-                .to_digit(2)
-                .unwrap();
+            let length_type_id = read_bits(index, 1)?;
 
             index += 1;
             bits_used += 1;
 
             let shift = match length_type_id {
-                0 => TOTAL_BIT_LEN_LEN,
-                1 => SUB_PACKETS_NUM_LEN,
-                _ => unreachable!(),
+                "0" => TOTAL_BIT_LEN_LEN,
+                _ => SUB_PACKETS_NUM_LEN,
             };
 
-            let value = usize::from_str_radix(&packet_string[index..index + shift], 2).unwrap();
+            let value = usize::from_str_radix(read_bits(index, shift)?, 2).unwrap();
 
             index += shift;
             bits_used += shift;
 
-            for packet_body in parsing_stack.iter_mut() {
-                if let Bits(bits_num) = packet_body {
-                    *packet_body = Bits(*bits_num - bits_used);
-                }
-            }
-
-            match packet_body {
-                Bits(bits_num) => {
-                    parsing_stack.push(Bits(bits_num - bits_used));
-                }
-                SubPackets(sub_packets_num) => {
-                    parsing_stack.push(SubPackets(sub_packets_num - 1));
-                }
-            }
+            consume_bits(&mut parsing_stack, packet_body, bits_used, index)?;
 
             match length_type_id {
-                0 => parsing_stack.push(Bits(value)),
-                1 => parsing_stack.push(SubPackets(value)),
-                _ => unreachable!(),
+                "0" => parsing_stack.push(Bits(value)),
+                _ => parsing_stack.push(SubPackets(value)),
             };
         }
     }
 
     packet_stack.pop();
 
-    packet_stack
+    Ok(packet_stack)
 }
 
 #[aoc(day16, part1)]
@@ -224,6 +241,29 @@ fn part2(packets: &[Packet]) -> Option<u64> {
     }
 }
 
+/// Day 16: Packet Decoder.
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Packet>;
+    type Part1 = u64;
+    type Part2 = Option<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,21 +283,21 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT_1)), 16);
-        assert_eq!(part1(&parse_input(TEST_INPUT_2)), 12);
-        assert_eq!(part1(&parse_input(TEST_INPUT_3)), 23);
-        assert_eq!(part1(&parse_input(TEST_INPUT_4)), 31);
+        assert_eq!(part1(&parse_input(TEST_INPUT_1).unwrap()), 16);
+        assert_eq!(part1(&parse_input(TEST_INPUT_2).unwrap()), 12);
+        assert_eq!(part1(&parse_input(TEST_INPUT_3).unwrap()), 23);
+        assert_eq!(part1(&parse_input(TEST_INPUT_4).unwrap()), 31);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT_5)), Some(3));
-        assert_eq!(part2(&parse_input(TEST_INPUT_6)), Some(54));
-        assert_eq!(part2(&parse_input(TEST_INPUT_7)), Some(7));
-        assert_eq!(part2(&parse_input(TEST_INPUT_8)), Some(9));
-        assert_eq!(part2(&parse_input(TEST_INPUT_9)), Some(1));
-        assert_eq!(part2(&parse_input(TEST_INPUT_10)), Some(0));
-        assert_eq!(part2(&parse_input(TEST_INPUT_11)), Some(0));
-        assert_eq!(part2(&parse_input(TEST_INPUT_12)), Some(1));
+        assert_eq!(part2(&parse_input(TEST_INPUT_5).unwrap()), Some(3));
+        assert_eq!(part2(&parse_input(TEST_INPUT_6).unwrap()), Some(54));
+        assert_eq!(part2(&parse_input(TEST_INPUT_7).unwrap()), Some(7));
+        assert_eq!(part2(&parse_input(TEST_INPUT_8).unwrap()), Some(9));
+        assert_eq!(part2(&parse_input(TEST_INPUT_9).unwrap()), Some(1));
+        assert_eq!(part2(&parse_input(TEST_INPUT_10).unwrap()), Some(0));
+        assert_eq!(part2(&parse_input(TEST_INPUT_11).unwrap()), Some(0));
+        assert_eq!(part2(&parse_input(TEST_INPUT_12).unwrap()), Some(1));
     }
 }
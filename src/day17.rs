@@ -1,27 +1,71 @@
+use crate::error::{next_part, parse_number, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::{HashMap, HashSet};
 
+const DAY: u8 = 17;
 const PREFIX: &str = "target area: ";
 
-struct TargetArea {
+pub struct TargetArea {
     x_min: i32,
     x_max: i32,
     y_min: i32,
     y_max: i32,
 }
 
+fn parse_range(input: &str, range: &str, axis: &str) -> Result<(i32, i32), ParseError> {
+    let bounds = range
+        .strip_prefix(axis)
+        .and_then(|r| r.strip_prefix('='))
+        .ok_or_else(|| ParseError::new(DAY, input, range, format!("expected {} range", axis)))?;
+    let mut split = bounds.split("..");
+
+    let min = parse_number(
+        DAY,
+        input,
+        next_part(DAY, range, &mut split, "lower bound")?,
+    )?;
+    let max = parse_number(
+        DAY,
+        input,
+        next_part(DAY, range, &mut split, "upper bound")?,
+    )?;
+
+    if min > max {
+        return Err(ParseError::new(DAY, input, range, "empty range"));
+    }
+
+    Ok((min, max))
+}
+
 #[aoc_generator(day17)]
-fn parse_input(input: &str) -> TargetArea {
-    let mut split = input[PREFIX.len()..]
-        .split(", ")
-        .flat_map(|r| r[2..].split("..").map(|c| c.parse().unwrap()));
-
-    TargetArea {
-        x_min: split.next().unwrap(),
-        x_max: split.next().unwrap(),
-        y_min: split.next().unwrap(),
-        y_max: split.next().unwrap(),
+fn parse_input(input: &str) -> Result<TargetArea, ParseError> {
+    let input: &str = &normalize(input);
+
+    let ranges = input
+        .strip_prefix(PREFIX)
+        .ok_or_else(|| ParseError::new(DAY, input, input, "expected target area"))?;
+    let mut split = ranges.split(", ");
+
+    let (x_min, x_max) = parse_range(input, next_part(DAY, input, &mut split, "x range")?, "x")?;
+    let (y_min, y_max) = parse_range(input, next_part(DAY, input, &mut split, "y range")?, "y")?;
+
+    if x_min <= 0 || y_max >= 0 {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            ranges,
+            "target area must lie below and to the right of the launcher",
+        ));
     }
+
+    Ok(TargetArea {
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+    })
 }
 
 #[aoc(day17, part1)]
@@ -117,6 +161,29 @@ fn part2(target_area: &TargetArea) -> usize {
     velocities.len()
 }
 
+/// Day 17: Trick Shot.
+pub struct Day17;
+
+impl Solver for Day17 {
+    const DAY: u8 = DAY;
+
+    type Input = TargetArea;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,11 +192,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 45);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 45);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 112);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 112);
     }
 }
@@ -1,9 +1,14 @@
 use crate::day18::Token::*;
+use crate::error::{char_at, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, AddAssign};
 
+const DAY: u8 = 18;
+
 #[derive(Clone)]
 enum Token {
     LeftBracket,
@@ -23,6 +28,40 @@ impl From<char> for Token {
     }
 }
 
+/// Checks that `snailfish_number_str[index..]` starts with a well-formed element
+/// and returns the index right after it.
+fn validate_element(snailfish_number_str: &str, index: usize) -> Result<usize, ParseError> {
+    let expect = |index: usize, expected: char, message: &str| {
+        if snailfish_number_str[index..].starts_with(expected) {
+            Ok(index + 1)
+        } else {
+            Err(ParseError::new(
+                DAY,
+                snailfish_number_str,
+                char_at(snailfish_number_str, index),
+                message,
+            ))
+        }
+    };
+
+    match snailfish_number_str[index..].chars().next() {
+        Some('[') => {
+            let index = validate_element(snailfish_number_str, index + 1)?;
+            let index = expect(index, ',', "expected `,`")?;
+            let index = validate_element(snailfish_number_str, index)?;
+
+            expect(index, ']', "expected `]`")
+        }
+        Some(c) if c.is_ascii_digit() => Ok(index + 1),
+        _ => Err(ParseError::new(
+            DAY,
+            snailfish_number_str,
+            char_at(snailfish_number_str, index),
+            "expected `[` or a digit",
+        )),
+    }
+}
+
 impl Display for Token {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,11 +74,35 @@ impl Display for Token {
 }
 
 #[derive(Clone)]
-struct SnailfishNumber(VecDeque<Token>);
+pub struct SnailfishNumber(VecDeque<Token>);
+
+impl TryFrom<&str> for SnailfishNumber {
+    type Error = ParseError;
+
+    fn try_from(snailfish_number_str: &str) -> Result<Self, Self::Error> {
+        if !snailfish_number_str.starts_with('[') {
+            return Err(ParseError::new(
+                DAY,
+                snailfish_number_str,
+                char_at(snailfish_number_str, 0),
+                "expected `[`",
+            ));
+        }
+
+        let end = validate_element(snailfish_number_str, 0)?;
 
-impl From<&str> for SnailfishNumber {
-    fn from(snailfish_number_str: &str) -> Self {
-        SnailfishNumber(snailfish_number_str.chars().map(|c| c.into()).collect())
+        if end != snailfish_number_str.len() {
+            return Err(ParseError::new(
+                DAY,
+                snailfish_number_str,
+                &snailfish_number_str[end..],
+                "unexpected text",
+            ));
+        }
+
+        Ok(SnailfishNumber(
+            snailfish_number_str.chars().map(|c| c.into()).collect(),
+        ))
     }
 }
 
@@ -218,8 +281,17 @@ impl AddAssign<&SnailfishNumber> for SnailfishNumber {
 }
 
 #[aoc_generator(day18)]
-fn parse_input(input: &str) -> Vec<SnailfishNumber> {
-    input.lines().map(|l| l.into()).collect()
+fn parse_input(input: &str) -> Result<Vec<SnailfishNumber>, ParseError> {
+    let input: &str = &normalize(input);
+
+    if input.is_empty() {
+        return Err(ParseError::new(DAY, input, input, "empty homework"));
+    }
+
+    input
+        .lines()
+        .map(|l| SnailfishNumber::try_from(l).map_err(|e| e.within(input, l)))
+        .collect()
 }
 
 fn final_sum(math_homework: &[SnailfishNumber]) -> SnailfishNumber {
@@ -252,6 +324,29 @@ fn part2(math_homework: &[SnailfishNumber]) -> u32 {
     result
 }
 
+/// Day 18: Snailfish.
+pub struct Day18;
+
+impl Solver for Day18 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<SnailfishNumber>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,31 +427,36 @@ mod tests {
     #[test]
     fn explode_example() {
         assert_eq!(
-            SnailfishNumber::from(EXPLODE_EXAMPLE_INPUT_1)
+            SnailfishNumber::try_from(EXPLODE_EXAMPLE_INPUT_1)
+                .unwrap()
                 .reduce()
                 .to_string(),
             EXPLODE_EXAMPLE_RESULT_1.to_string()
         );
         assert_eq!(
-            SnailfishNumber::from(EXPLODE_EXAMPLE_INPUT_2)
+            SnailfishNumber::try_from(EXPLODE_EXAMPLE_INPUT_2)
+                .unwrap()
                 .reduce()
                 .to_string(),
             EXPLODE_EXAMPLE_RESULT_2.to_string()
         );
         assert_eq!(
-            SnailfishNumber::from(EXPLODE_EXAMPLE_INPUT_3)
+            SnailfishNumber::try_from(EXPLODE_EXAMPLE_INPUT_3)
+                .unwrap()
                 .reduce()
                 .to_string(),
             EXPLODE_EXAMPLE_RESULT_3.to_string()
         );
         assert_eq!(
-            SnailfishNumber::from(EXPLODE_EXAMPLE_INPUT_4)
+            SnailfishNumber::try_from(EXPLODE_EXAMPLE_INPUT_4)
+                .unwrap()
                 .reduce()
                 .to_string(),
             EXPLODE_EXAMPLE_RESULT_4.to_string()
         );
         assert_eq!(
-            SnailfishNumber::from(EXPLODE_EXAMPLE_INPUT_5)
+            SnailfishNumber::try_from(EXPLODE_EXAMPLE_INPUT_5)
+                .unwrap()
                 .reduce()
                 .to_string(),
             EXPLODE_EXAMPLE_RESULT_5.to_string()
@@ -366,7 +466,8 @@ mod tests {
     #[test]
     fn sum_example() {
         assert_eq!(
-            (&SnailfishNumber::from(SUM_EXAMPLE_LHS) + &SnailfishNumber::from(SUM_EXAMPLE_RHS))
+            (&SnailfishNumber::try_from(SUM_EXAMPLE_LHS).unwrap()
+                + &SnailfishNumber::try_from(SUM_EXAMPLE_RHS).unwrap())
                 .to_string(),
             SUM_EXAMPLE_RESULT.to_string()
         );
@@ -375,55 +476,91 @@ mod tests {
     #[test]
     fn final_sum_example() {
         assert_eq!(
-            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_1)).to_string(),
+            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_1).unwrap()).to_string(),
             FINAL_SUM_TEST_RESULT_1.to_string()
         );
 
         assert_eq!(
-            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_2)).to_string(),
+            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_2).unwrap()).to_string(),
             FINAL_SUM_TEST_RESULT_2.to_string()
         );
 
         assert_eq!(
-            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_3)).to_string(),
+            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_3).unwrap()).to_string(),
             FINAL_SUM_TEST_RESULT_3.to_string()
         );
 
         assert_eq!(
-            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_4)).to_string(),
+            final_sum(&parse_input(FINAL_SUM_TEST_INPUT_4).unwrap()).to_string(),
             FINAL_SUM_TEST_RESULT_4.to_string()
         );
     }
 
     #[test]
     fn magnitude_example() {
-        assert_eq!(SnailfishNumber::from(MAGNITUDE_EXAMPLE_1).magnitude(), 29);
-        assert_eq!(SnailfishNumber::from(MAGNITUDE_EXAMPLE_2).magnitude(), 21);
-        assert_eq!(SnailfishNumber::from(MAGNITUDE_EXAMPLE_3).magnitude(), 129);
-        assert_eq!(SnailfishNumber::from(MAGNITUDE_EXAMPLE_4).magnitude(), 143);
         assert_eq!(
-            SnailfishNumber::from(MAGNITUDE_EXAMPLE_5).magnitude(),
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_1)
+                .unwrap()
+                .magnitude(),
+            29
+        );
+        assert_eq!(
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_2)
+                .unwrap()
+                .magnitude(),
+            21
+        );
+        assert_eq!(
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_3)
+                .unwrap()
+                .magnitude(),
+            129
+        );
+        assert_eq!(
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_4)
+                .unwrap()
+                .magnitude(),
+            143
+        );
+        assert_eq!(
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_5)
+                .unwrap()
+                .magnitude(),
             1_384
         );
-        assert_eq!(SnailfishNumber::from(MAGNITUDE_EXAMPLE_6).magnitude(), 445);
-        assert_eq!(SnailfishNumber::from(MAGNITUDE_EXAMPLE_7).magnitude(), 791);
         assert_eq!(
-            SnailfishNumber::from(MAGNITUDE_EXAMPLE_8).magnitude(),
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_6)
+                .unwrap()
+                .magnitude(),
+            445
+        );
+        assert_eq!(
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_7)
+                .unwrap()
+                .magnitude(),
+            791
+        );
+        assert_eq!(
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_8)
+                .unwrap()
+                .magnitude(),
             1_137
         );
         assert_eq!(
-            SnailfishNumber::from(MAGNITUDE_EXAMPLE_9).magnitude(),
+            SnailfishNumber::try_from(MAGNITUDE_EXAMPLE_9)
+                .unwrap()
+                .magnitude(),
             3_488
         );
     }
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 4_140);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 4_140);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 3_993);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 3_993);
     }
 }
@@ -1,52 +1,48 @@
+use crate::error::{next_part, ParseError};
+use crate::geometry::Vec3;
+use crate::json::Json;
+use crate::solver::Solver;
+use crate::text::{normalize, paragraphs};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashSet;
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
-struct Vec3 {
-    x: i32,
-    y: i32,
-    z: i32,
-}
+const DAY: u8 = 19;
 
-impl From<(i32, i32, i32)> for Vec3 {
-    fn from((x, y, z): (i32, i32, i32)) -> Self {
-        Vec3 { x, y, z }
-    }
-}
+type Beacon = Vec3<i32>;
 
-impl From<Vec3> for (i32, i32, i32) {
-    fn from(vec3: Vec3) -> Self {
-        (vec3.x, vec3.y, vec3.z)
-    }
-}
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Scanner(HashSet<Beacon>);
 
-type Beacon = Vec3;
+impl TryFrom<&str> for Scanner {
+    type Error = ParseError;
 
-impl From<&str> for Beacon {
-    fn from(coordinates_str: &str) -> Self {
-        let mut coordinates = coordinates_str
-            .split(',')
-            .map(|s| s.parse::<i32>().unwrap());
+    fn try_from(scanner_str: &str) -> Result<Self, Self::Error> {
+        let mut lines = scanner_str.lines();
 
-        Self {
-            x: coordinates.next().unwrap(),
-            y: coordinates.next().unwrap(),
-            z: coordinates.next().unwrap(),
-        }
-    }
-}
+        let header = next_part(DAY, scanner_str, &mut lines, "scanner header")?;
 
-#[derive(Eq, PartialEq, Clone, Debug)]
-struct Scanner(HashSet<Beacon>);
+        if !header.starts_with("--- scanner") {
+            return Err(ParseError::new(
+                DAY,
+                scanner_str,
+                header,
+                "expected scanner header",
+            ));
+        }
 
-impl From<&str> for Scanner {
-    fn from(scanner_str: &str) -> Self {
-        Scanner(scanner_str.lines().skip(1).map(|l| l.into()).collect())
+        Ok(Scanner(
+            lines
+                .map(|l| Beacon::from_csv(DAY, l).map_err(|e| e.within(scanner_str, l)))
+                .collect::<Result<_, _>>()?,
+        ))
     }
 }
 
 impl Scanner {
-    fn overlap_regions_with(&self, another_scanner: &Scanner) -> (HashSet<Beacon>, Scanner, Vec3) {
+    fn overlap_regions_with(
+        &self,
+        another_scanner: &Scanner,
+    ) -> (HashSet<Beacon>, Scanner, Vec3<i32>) {
         for transformed_scanner in another_scanner.all_transformations() {
             for beacon in self.0.iter() {
                 for another_beacon in transformed_scanner.0.iter() {
@@ -54,29 +50,13 @@ impl Scanner {
                         transformed_scanner
                             .0
                             .iter()
-                            .map(|b| {
-                                (
-                                    b.x + beacon.x - another_beacon.x,
-                                    b.y + beacon.y - another_beacon.y,
-                                    b.z + beacon.z - another_beacon.z,
-                                )
-                                    .into()
-                            })
+                            .map(|b| *b + *beacon - *another_beacon)
                             .collect(),
                     );
 
                     if self.0.intersection(&updated_scanner.0).count() >= 12 {
                         let result = updated_scanner.0.union(&self.0).cloned().collect();
-                        return (
-                            result,
-                            updated_scanner,
-                            (
-                                beacon.x - another_beacon.x,
-                                beacon.y - another_beacon.y,
-                                beacon.z - another_beacon.z,
-                            )
-                                .into(),
-                        );
+                        return (result, updated_scanner, *beacon - *another_beacon);
                     }
                 }
             }
@@ -85,113 +65,124 @@ impl Scanner {
         (
             HashSet::with_capacity(0),
             another_scanner.to_owned(),
-            (0, 0, 0).into(),
+            Vec3::default(),
         )
     }
 
     fn all_transformations(&self) -> Vec<Scanner> {
-        Vec::from([
-            Scanner(self.0.iter().map(|b| (b.x, b.y, b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.x, b.z, -b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.x, -b.y, -b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.x, -b.z, b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.x, b.y, -b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.x, b.z, b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.x, -b.y, b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.x, -b.z, -b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.y, b.x, -b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.y, -b.x, b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.y, b.z, b.x).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.y, -b.z, -b.x).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.y, b.x, b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.y, -b.x, -b.z).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.y, b.z, -b.x).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.y, -b.z, b.x).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.z, b.x, b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.z, -b.x, -b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.z, b.y, -b.x).into()).collect()),
-            Scanner(self.0.iter().map(|b| (b.z, -b.y, b.x).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.z, b.x, -b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.z, -b.x, b.y).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.z, b.y, b.x).into()).collect()),
-            Scanner(self.0.iter().map(|b| (-b.z, -b.y, -b.x).into()).collect()),
-        ])
+        let mut transformations = vec![HashSet::with_capacity(self.0.len()); 24];
+
+        for beacon in self.0.iter() {
+            for (transformation, rotated) in transformations.iter_mut().zip(beacon.rotations()) {
+                transformation.insert(rotated);
+            }
+        }
+
+        transformations.into_iter().map(Scanner).collect()
     }
 }
 
 #[aoc_generator(day19)]
-fn parse_input(input: &str) -> Vec<Scanner> {
-    input.split("\n\n").map(|s| s.into()).collect()
+fn parse_input(input: &str) -> Result<Vec<Scanner>, ParseError> {
+    let input: &str = &normalize(input);
+
+    if input.is_empty() {
+        return Err(ParseError::new(DAY, input, input, "no scanners"));
+    }
+
+    paragraphs(input)
+        .map(|s| Scanner::try_from(s).map_err(|e| e.within(input, s)))
+        .collect()
 }
 
-#[aoc(day19, part1)]
-fn part1(scanners: &[Scanner]) -> usize {
-    let mut beacons = HashSet::new();
-    let mut scanners = scanners.to_owned();
+/// Beacons and scanners in the frame of reference of the first scanner.
+pub struct Alignment {
+    pub beacons: HashSet<Beacon>,
+    /// Scanner positions in input order, `None` for scanners sharing no beacons with the others.
+    pub scanners: Vec<Option<Beacon>>,
+}
 
-    let mut adjusted_scanners = vec![scanners.pop().unwrap()];
+pub fn align(scanners: &[Scanner]) -> Alignment {
+    let mut beacons = scanners[0].0.to_owned();
+    let mut positions = vec![None; scanners.len()];
+    positions[0] = Some(Beacon::default());
 
-    while !scanners.is_empty() {
-        let mut non_overlapping = Vec::new();
+    let mut scanners = scanners.iter().cloned().enumerate().collect::<Vec<_>>();
+    let mut adjusted_scanners = vec![scanners.remove(0).1];
 
-        let adjusted_scanner = adjusted_scanners.pop().unwrap();
+    while let Some(adjusted_scanner) = adjusted_scanners.pop() {
+        let mut non_overlapping = Vec::new();
 
-        for scanner in scanners.into_iter() {
-            let (new_beacons, scanner, _) = adjusted_scanner.overlap_regions_with(&scanner);
+        for (i, scanner) in scanners.into_iter() {
+            let (new_beacons, scanner, position) = adjusted_scanner.overlap_regions_with(&scanner);
 
             if !new_beacons.is_empty() {
                 adjusted_scanners.push(scanner);
+                positions[i] = Some(position);
                 beacons.extend(new_beacons);
             } else {
-                non_overlapping.push(scanner);
+                non_overlapping.push((i, scanner));
             }
         }
 
         scanners = non_overlapping;
     }
 
-    beacons.len()
+    Alignment {
+        beacons,
+        scanners: positions,
+    }
+}
+
+#[aoc(day19, part1)]
+fn part1(scanners: &[Scanner]) -> usize {
+    align(scanners).beacons.len()
 }
 
 #[aoc(day19, part2)]
 fn part2(scanners: &[Scanner]) -> i32 {
-    let mut scanners = scanners.to_owned();
+    let positions = align(scanners)
+        .scanners
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
 
-    let mut adjusted_scanners = vec![scanners.pop().unwrap()];
-    let mut distances = vec![Vec3 { x: 0, y: 0, z: 0 }];
+    let mut result = 0;
 
-    while !scanners.is_empty() {
-        let mut non_overlapping = Vec::new();
+    for (i, position1) in positions.iter().enumerate() {
+        for position2 in positions.iter().skip(i) {
+            result = result.max(position1.manhattan(*position2))
+        }
+    }
 
-        let adjusted_scanner = adjusted_scanners.pop().unwrap();
+    result
+}
 
-        for scanner in scanners.into_iter() {
-            let (new_beacons, scanner, distance) = adjusted_scanner.overlap_regions_with(&scanner);
+/// Day 19: Beacon Scanner.
+pub struct Day19;
 
-            if !new_beacons.is_empty() {
-                adjusted_scanners.push(scanner);
-                distances.push(distance);
-            } else {
-                non_overlapping.push(scanner);
-            }
-        }
+impl Solver for Day19 {
+    const DAY: u8 = DAY;
 
-        scanners = non_overlapping;
+    type Input = Vec<Scanner>;
+    type Part1 = usize;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    let mut result = 0;
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
 
-    for (i, distance1) in distances.iter().enumerate() {
-        for distance2 in distances.iter().skip(i) {
-            result = result.max(
-                (distance1.x - distance2.x).abs()
-                    + (distance1.y - distance2.y).abs()
-                    + (distance1.z - distance2.z).abs(),
-            )
-        }
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
     }
 
-    result
+    fn details(input: &Self::Input) -> Vec<(&'static str, Json)> {
+        vec![("scanners", align(input).scanners.into())]
+    }
 }
 
 #[cfg(test)]
@@ -337,11 +328,25 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 79);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 79);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 3_621);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 3_621);
+    }
+
+    #[test]
+    fn scanner_positions() {
+        assert_eq!(
+            align(&parse_input(TEST_INPUT).unwrap()).scanners,
+            vec![
+                Some(Vec3::new(0, 0, 0)),
+                Some(Vec3::new(68, -1246, -43)),
+                Some(Vec3::new(1105, -1205, 1229)),
+                Some(Vec3::new(-92, -2380, -20)),
+                Some(Vec3::new(-20, -1133, 1061)),
+            ]
+        );
     }
 }
@@ -1,7 +1,14 @@
 use crate::day20::Pixel::*;
+use crate::error::{char_at, next_part, ParseError};
+use crate::frames::{Frames, Render, Rgb};
+use crate::grid::{Grid, Position};
+use crate::solver::Solver;
+use crate::text::{normalize, paragraphs};
 use aoc_runner_derive::{aoc, aoc_generator};
-use std::collections::HashMap;
-use std::ops::Add;
+use std::fmt::{Display, Formatter};
+
+const DAY: u8 = 20;
+const ALGORITHM_LEN: usize = 512;
 
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 enum Pixel {
@@ -9,108 +16,96 @@ enum Pixel {
     Dark,
 }
 
-impl From<char> for Pixel {
-    fn from(c: char) -> Self {
+impl Pixel {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '#' => Light,
-            '.' => Dark,
-            _ => unreachable!(),
+            '#' => Some(Light),
+            '.' => Some(Dark),
+            _ => None,
         }
     }
-}
 
-impl From<Pixel> for usize {
-    fn from(pixel: Pixel) -> Self {
-        match pixel {
-            Light => 1,
-            Dark => 0,
-        }
-    }
-}
+    fn parse(source: &str, line: &str, index: usize) -> Result<Self, ParseError> {
+        let c = char_at(line, index);
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
-struct Vec2 {
-    x: i32,
-    y: i32,
-}
-
-type Position = Vec2;
-
-impl From<(i32, i32)> for Vec2 {
-    fn from((x, y): (i32, i32)) -> Self {
-        Self { x, y }
+        c.chars()
+            .next()
+            .and_then(Pixel::from_char)
+            .ok_or_else(|| ParseError::new(DAY, source, c, "invalid pixel"))
     }
 }
 
-impl From<Vec2> for (i32, i32) {
-    fn from(vec2: Vec2) -> Self {
-        (vec2.x, vec2.y)
+impl Display for Pixel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Light => write!(f, "#"),
+            Dark => write!(f, "."),
+        }
     }
 }
 
-impl Add for Vec2 {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+impl Render for Pixel {
+    fn glyph(&self) -> char {
+        match self {
+            Light => '#',
+            Dark => '.',
         }
     }
-}
 
-#[derive(Eq, PartialEq, Clone, Debug)]
-struct Image {
-    pixels: HashMap<Position, Pixel>,
-    top_left: Position,
-    bottom_right: Position,
+    fn color(&self) -> Rgb {
+        match self {
+            Light => [255, 255, 255],
+            Dark => [0, 0, 0],
+        }
+    }
 }
 
-impl From<&str> for Image {
-    fn from(image_str: &str) -> Self {
-        Image {
-            pixels: image_str
-                .lines()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .map(move |(x, c)| ((x as i32, y as i32).into(), c.into()))
-                })
-                .collect(),
-            top_left: (0, 0).into(),
-            bottom_right: (
-                image_str.lines().next().unwrap().len() as i32,
-                image_str.lines().count() as i32,
-            )
-                .into(),
+impl From<Pixel> for usize {
+    fn from(pixel: Pixel) -> Self {
+        match pixel {
+            Light => 1,
+            Dark => 0,
         }
     }
 }
 
+type Image = Grid<Pixel>;
+
 #[derive(Clone, Debug)]
-struct Processor {
-    image_enhancement_algorithm: [Pixel; 512],
+pub struct Processor {
+    image_enhancement_algorithm: [Pixel; ALGORITHM_LEN],
     input_image: Image,
     outside_pixels_kind: Pixel,
 }
 
-impl From<&str> for Processor {
-    fn from(processor_str: &str) -> Self {
-        let mut input_split = processor_str.split("\n\n");
-        let image_enhancement_algorithm = input_split
-            .next()
-            .unwrap()
-            .chars()
-            .filter(|c| *c == '#' || *c == '.')
-            .map(|c| c.into())
-            .collect::<Vec<Pixel>>();
-
-        Processor {
-            image_enhancement_algorithm: image_enhancement_algorithm.try_into().unwrap(),
-            input_image: input_split.next().unwrap().into(),
+impl TryFrom<&str> for Processor {
+    type Error = ParseError;
+
+    fn try_from(processor_str: &str) -> Result<Self, Self::Error> {
+        let mut input_split = paragraphs(processor_str);
+        let algorithm_str = next_part(DAY, processor_str, &mut input_split, "algorithm")?;
+        let image_enhancement_algorithm = algorithm_str
+            .lines()
+            .flat_map(|line| {
+                line.char_indices()
+                    .map(move |(x, _)| Pixel::parse(processor_str, line, x))
+            })
+            .collect::<Result<Vec<Pixel>, _>>()?;
+        let image_str = next_part(DAY, processor_str, &mut input_split, "input image")?;
+
+        Ok(Processor {
+            image_enhancement_algorithm: image_enhancement_algorithm.try_into().map_err(|_| {
+                ParseError::new(
+                    DAY,
+                    processor_str,
+                    algorithm_str,
+                    format!("algorithm must have {} pixels", ALGORITHM_LEN),
+                )
+            })?,
+            input_image: Grid::parse(DAY, image_str, Pixel::from_char, "invalid pixel")
+                .map_err(|e| e.within(processor_str, image_str))?,
             outside_pixels_kind: Dark,
-        }
+        })
     }
 }
 
@@ -118,37 +113,39 @@ const DARK_FRAGMENT_BINARY: usize = 0b000000000;
 const LIGHT_FRAGMENT_BINARY: usize = 0b111111111;
 
 impl Processor {
+    /// Computes a pixel of the enhanced image, which extends one pixel further
+    /// than the input image on every side.
     fn apply_algorithm_to_pixel(&self, pixel_position: Position) -> Pixel {
-        *self
-            .image_enhancement_algorithm
-            .get(self.pixel_to_binary(&pixel_position))
-            .unwrap()
+        self.image_enhancement_algorithm[self.pixel_to_binary(pixel_position)]
     }
 
     fn apply_algorithm(&mut self) {
-        let mut new_image = Image {
-            pixels: self.input_image.pixels.to_owned(),
-            top_left: self.input_image.top_left + (-1, -1).into(),
-            bottom_right: self.input_image.bottom_right + (1, 1).into(),
-        };
+        let new_image = Grid::from_fn(
+            self.input_image.width() + 2,
+            self.input_image.height() + 2,
+            |position| self.apply_algorithm_to_pixel(position),
+        );
 
-        let new_outside_pixels_kind = match self.outside_pixels_kind {
+        self.outside_pixels_kind = match self.outside_pixels_kind {
             Light => self.image_enhancement_algorithm[LIGHT_FRAGMENT_BINARY],
             Dark => self.image_enhancement_algorithm[DARK_FRAGMENT_BINARY],
         };
+        self.input_image = new_image;
+    }
 
-        for x in new_image.top_left.x..=new_image.bottom_right.x {
-            for y in new_image.top_left.y..=new_image.bottom_right.y {
-                let pixel = new_image
-                    .pixels
-                    .entry(Position { x, y })
-                    .or_insert(new_outside_pixels_kind);
-                *pixel = self.apply_algorithm_to_pixel(Position { x, y });
-            }
+    /// Snapshots of the image before enhancing it and after each of `steps` enhancements.
+    pub fn record(&self, steps: usize) -> Frames {
+        let mut processor = self.to_owned();
+        let mut frames = Frames::default();
+
+        frames.capture(&processor.input_image);
+
+        for _ in 0..steps {
+            processor.apply_algorithm();
+            frames.capture(&processor.input_image);
         }
 
-        self.input_image = new_image;
-        self.outside_pixels_kind = new_outside_pixels_kind;
+        frames
     }
 
     fn apply_algorithm_n_times(mut self, n: usize) -> Self {
@@ -158,21 +155,19 @@ impl Processor {
 
     fn count_pixels(&self, pixels_kind: Pixel) -> usize {
         self.input_image
-            .pixels
             .values()
             .filter(|pixel| **pixel == pixels_kind)
             .count()
     }
 
-    fn pixel_to_binary(&self, pixel_position: &Position) -> usize {
+    fn pixel_to_binary(&self, pixel_position: Position) -> usize {
         let mut result = 0;
 
-        for y in -1..=1 {
-            for x in -1..=1 {
-                let pixel = self
-                    .input_image
-                    .pixels
-                    .get(&(*pixel_position + (x, y).into()))
+        for y in 0..3 {
+            for x in 0..3 {
+                let pixel = Position::new(pixel_position.x + x, pixel_position.y + y)
+                    .step((-2, -2))
+                    .and_then(|position| self.input_image.get(position))
                     .unwrap_or(&self.outside_pixels_kind);
 
                 result = result * 2 + usize::from(*pixel);
@@ -184,8 +179,10 @@ impl Processor {
 }
 
 #[aoc_generator(day20)]
-fn parse_input(input: &str) -> Processor {
-    input.into()
+fn parse_input(input: &str) -> Result<Processor, ParseError> {
+    let input: &str = &normalize(input);
+
+    input.try_into()
 }
 
 #[aoc(day20, part1)]
@@ -204,6 +201,29 @@ fn part2(processor: &Processor) -> usize {
         .count_pixels(Light)
 }
 
+/// Day 20: Trench Map.
+pub struct Day20;
+
+impl Solver for Day20 {
+    const DAY: u8 = DAY;
+
+    type Input = Processor;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,11 +244,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 35);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 35);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 3_351);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 3_351);
     }
 }
@@ -1,6 +1,10 @@
+use crate::error::{next_part, parse_number, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashMap;
 
+const DAY: u8 = 21;
 const PRACTICE_GAME_WINNING_SCORE: u64 = 1_000;
 const DIRAC_GAME_WINNING_SCORE: u64 = 21;
 const DETERMINISTIC_DIE_FACES: u64 = 100;
@@ -8,28 +12,34 @@ const DIRAC_DIE_FACES: u64 = 3;
 const TRACK_LEN: u64 = 10;
 const MAX_TURNS: usize = 10; //Each turn adds at least 1 to the score, but every other turn adds at least 1 + 3 = 4.
 
+fn parse_starting_position(input: &str, line: &str) -> Result<u64, ParseError> {
+    let position_str = line
+        .rsplit(": ")
+        .next()
+        .filter(|p| p.len() < line.len())
+        .ok_or_else(|| ParseError::new(DAY, input, line, "missing starting position"))?;
+
+    match parse_number(DAY, input, position_str)? {
+        position @ 1..=TRACK_LEN => Ok(position),
+        _ => Err(ParseError::new(
+            DAY,
+            input,
+            position_str,
+            "position out of track",
+        )),
+    }
+}
+
 #[aoc_generator(day21)]
-fn parse_input(input: &str) -> (u64, u64) {
+fn parse_input(input: &str) -> Result<(u64, u64), ParseError> {
+    let input: &str = &normalize(input);
+
     let mut lines = input.lines();
 
-    (
-        lines
-            .next()
-            .unwrap()
-            .chars()
-            .last()
-            .unwrap()
-            .to_digit(10)
-            .unwrap() as u64,
-        lines
-            .next()
-            .unwrap()
-            .chars()
-            .last()
-            .unwrap()
-            .to_digit(10)
-            .unwrap() as u64,
-    )
+    Ok((
+        parse_starting_position(input, next_part(DAY, input, &mut lines, "player 1")?)?,
+        parse_starting_position(input, next_part(DAY, input, &mut lines, "player 2")?)?,
+    ))
 }
 
 fn deterministic_turn(position: &mut u64, die: &mut u64, score: &mut u64, rolls: &mut u64) {
@@ -93,9 +103,7 @@ fn universes_per_turn(
     let mut winning_by_turn = [0u64; MAX_TURNS + 1];
     let mut non_winning_by_turn = [0u64; MAX_TURNS + 1];
 
-    while !stack.is_empty() {
-        let node = stack.pop().unwrap();
-
+    while let Some(node) = stack.pop() {
         if node.score >= DIRAC_GAME_WINNING_SCORE {
             winning_by_turn[node.turns] += node.universes;
         } else {
@@ -153,6 +161,29 @@ fn part2((p1_position, p2_position): &(u64, u64)) -> u64 {
     p2_wins.max(p1_wins)
 }
 
+/// Day 21: Dirac Dice.
+pub struct Day21;
+
+impl Solver for Day21 {
+    const DAY: u8 = DAY;
+
+    type Input = (u64, u64);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,11 +193,22 @@ Player 2 starting position: 8";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 739_785);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 739_785);
+    }
+
+    #[test]
+    fn padded_input() {
+        let input =
+            "\u{feff}Player 1 starting position: 4 \r\nPlayer 2 starting position: 8\t\r\n\n";
+
+        assert_eq!(part1(&parse_input(input).unwrap()), 739_785);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 444_356_092_776_315);
+        assert_eq!(
+            part2(&parse_input(TEST_INPUT).unwrap()),
+            444_356_092_776_315
+        );
     }
 }
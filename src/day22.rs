@@ -1,19 +1,11 @@
 use crate::day22::CubeState::*;
+use crate::error::{next_part, parse_number, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::ops::RangeInclusive;
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
-struct Vec3 {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl From<(i32, i32, i32)> for Vec3 {
-    fn from((x, y, z): (i32, i32, i32)) -> Self {
-        Vec3 { x, y, z }
-    }
-}
+const DAY: u8 = 22;
 
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 enum CubeState {
@@ -21,12 +13,14 @@ enum CubeState {
     Off,
 }
 
-impl From<&str> for CubeState {
-    fn from(state: &str) -> Self {
+impl TryFrom<&str> for CubeState {
+    type Error = ParseError;
+
+    fn try_from(state: &str) -> Result<Self, Self::Error> {
         match state {
-            "on" => On,
-            "off" => Off,
-            _ => unreachable!(),
+            "on" => Ok(On),
+            "off" => Ok(Off),
+            _ => Err(ParseError::new(DAY, state, state, "unknown cube state")),
         }
     }
 }
@@ -38,22 +32,54 @@ struct Cuboid {
     z: RangeInclusive<i32>,
 }
 
-impl From<&str> for Cuboid {
-    fn from(cuboid: &str) -> Self {
+fn parse_range(cuboid: &str, range: &str, axis: &str) -> Result<(i32, i32), ParseError> {
+    let bounds = range
+        .strip_prefix(axis)
+        .and_then(|r| r.strip_prefix('='))
+        .ok_or_else(|| ParseError::new(DAY, cuboid, range, format!("expected {} range", axis)))?;
+    let mut split = bounds.split("..");
+
+    let min = parse_number(
+        DAY,
+        cuboid,
+        next_part(DAY, range, &mut split, "lower bound")?,
+    )?;
+    let max = parse_number(
+        DAY,
+        cuboid,
+        next_part(DAY, range, &mut split, "upper bound")?,
+    )?;
+
+    if min > max {
+        return Err(ParseError::new(DAY, cuboid, range, "empty range"));
+    }
+
+    Ok((min, max))
+}
+
+impl TryFrom<&str> for Cuboid {
+    type Error = ParseError;
+
+    fn try_from(cuboid: &str) -> Result<Self, Self::Error> {
         let mut range_split = cuboid.split(',');
 
-        let mut x_range = range_split.next().unwrap()[2..].split("..");
-        let mut y_range = range_split.next().unwrap()[2..].split("..");
-        let mut z_range = range_split.next().unwrap()[2..].split("..");
-
-        Cuboid::new(
-            x_range.next().unwrap().parse().unwrap(),
-            x_range.next().unwrap().parse().unwrap(),
-            y_range.next().unwrap().parse().unwrap(),
-            y_range.next().unwrap().parse().unwrap(),
-            z_range.next().unwrap().parse().unwrap(),
-            z_range.next().unwrap().parse().unwrap(),
-        )
+        let (x_min, x_max) = parse_range(
+            cuboid,
+            next_part(DAY, cuboid, &mut range_split, "x range")?,
+            "x",
+        )?;
+        let (y_min, y_max) = parse_range(
+            cuboid,
+            next_part(DAY, cuboid, &mut range_split, "y range")?,
+            "y",
+        )?;
+        let (z_min, z_max) = parse_range(
+            cuboid,
+            next_part(DAY, cuboid, &mut range_split, "z range")?,
+            "z",
+        )?;
+
+        Ok(Cuboid::new(x_min, x_max, y_min, y_max, z_min, z_max))
     }
 }
 
@@ -175,25 +201,35 @@ const INIT_PROCEDURE_REGION: Cuboid = Cuboid {
 };
 
 #[derive(Clone, Debug)]
-struct RebootStep {
+pub struct RebootStep {
     state: CubeState,
     cuboid: Cuboid,
 }
 
-impl From<&str> for RebootStep {
-    fn from(reboot_step: &str) -> Self {
+impl TryFrom<&str> for RebootStep {
+    type Error = ParseError;
+
+    fn try_from(reboot_step: &str) -> Result<Self, Self::Error> {
         let mut split = reboot_step.split_whitespace();
 
-        RebootStep {
-            state: split.next().unwrap().into(),
-            cuboid: split.next().unwrap().into(),
-        }
+        let state_str = next_part(DAY, reboot_step, &mut split, "cube state")?;
+        let cuboid_str = next_part(DAY, reboot_step, &mut split, "cuboid")?;
+
+        Ok(RebootStep {
+            state: CubeState::try_from(state_str).map_err(|e| e.within(reboot_step, state_str))?,
+            cuboid: Cuboid::try_from(cuboid_str).map_err(|e| e.within(reboot_step, cuboid_str))?,
+        })
     }
 }
 
 #[aoc_generator(day22)]
-fn parse_input(input: &str) -> Vec<RebootStep> {
-    input.lines().map(|line| line.into()).collect()
+fn parse_input(input: &str) -> Result<Vec<RebootStep>, ParseError> {
+    let input: &str = &normalize(input);
+
+    input
+        .lines()
+        .map(|line| RebootStep::try_from(line).map_err(|e| e.within(input, line)))
+        .collect()
 }
 
 fn non_overlapping_active_cuboids(reboot_steps: &[RebootStep]) -> Vec<Cuboid> {
@@ -259,9 +295,34 @@ fn part2(reboot_steps: &[RebootStep]) -> usize {
         .sum()
 }
 
+/// Day 22: Reactor Reboot.
+pub struct Day22;
+
+impl Solver for Day22 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<RebootStep>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::inputs::Rng;
+    use crate::property::{check, no_shrink, shrink_vec};
 
     static TEST_INPUT: &str = r"on x=10..12,y=10..12,z=10..12
 on x=11..13,y=11..13,z=11..13
@@ -354,17 +415,106 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 39);
-        assert_eq!(part1(&parse_input(LARGER_TEST_INPUT)), 590_784);
-        assert_eq!(part1(&parse_input(VERY_LARGE_TEST_INPUT)), 474_140);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 39);
+        assert_eq!(part1(&parse_input(LARGER_TEST_INPUT).unwrap()), 590_784);
+        assert_eq!(part1(&parse_input(VERY_LARGE_TEST_INPUT).unwrap()), 474_140);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse_input(TEST_INPUT)), 39);
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), 39);
         assert_eq!(
-            part2(&parse_input(VERY_LARGE_TEST_INPUT)),
+            part2(&parse_input(VERY_LARGE_TEST_INPUT).unwrap()),
             2_758_514_936_282_235
         );
     }
+
+    /// Half the side of the cube the random steps stay in.
+    const RADIUS: i64 = 5;
+
+    type Step = (bool, [(i64, i64); 3]);
+
+    /// Steps inside a small cube centered on the origin or on the edge of the
+    /// initialization procedure region along each axis.
+    fn case(rng: &mut Rng) -> ([i64; 3], Vec<Step>) {
+        let center = [(); 3].map(|_| *rng.choose(&[-50, 0, 50]));
+        let steps = (0..rng.range(1, 8))
+            .map(|_| {
+                let ranges = center.map(|c| {
+                    let (a, b) = (rng.range(-RADIUS, RADIUS), rng.range(-RADIUS, RADIUS));
+                    (c + a.min(b), c + a.max(b))
+                });
+                (rng.ratio(2, 3), ranges)
+            })
+            .collect();
+
+        (center, steps)
+    }
+
+    /// Switches every cube of a dense grid step by step and counts the lit cubes, in
+    /// the initialization procedure region and in total.
+    fn naive_lit_cubes(center: [i64; 3], steps: &[Step]) -> (usize, usize) {
+        let side = (2 * RADIUS + 1) as usize;
+        let mut grid = vec![false; side.pow(3)];
+        let index = |p: [i64; 3]| {
+            (0..3).fold(0, |index, axis| {
+                index * side + (p[axis] - center[axis] + RADIUS) as usize
+            })
+        };
+        let cubes = |[x, y, z]: [(i64, i64); 3]| {
+            (x.0..=x.1).flat_map(move |i| {
+                (y.0..=y.1).flat_map(move |j| (z.0..=z.1).map(move |k| [i, j, k]))
+            })
+        };
+
+        for &(on, ranges) in steps {
+            for cube in cubes(ranges) {
+                grid[index(cube)] = on;
+            }
+        }
+
+        let all = center.map(|c| (c - RADIUS, c + RADIUS));
+        let lit = cubes(all).filter(|cube| grid[index(*cube)]);
+        let initialization = lit
+            .clone()
+            .filter(|cube| cube.iter().all(|c| (-50..=50).contains(c)))
+            .count();
+
+        (initialization, lit.count())
+    }
+
+    #[test]
+    fn volumes_match_grid_marking() {
+        check(
+            22,
+            case,
+            |(center, steps)| {
+                shrink_vec(steps, 1, no_shrink)
+                    .into_iter()
+                    .map(|steps| (*center, steps))
+                    .collect()
+            },
+            |(center, steps)| {
+                let input = steps
+                    .iter()
+                    .map(|(on, [x, y, z])| {
+                        format!(
+                            "{} x={}..{},y={}..{},z={}..{}",
+                            if *on { "on" } else { "off" },
+                            x.0,
+                            x.1,
+                            y.0,
+                            y.1,
+                            z.0,
+                            z.1
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let reboot_steps = parse_input(&input).unwrap();
+
+                (part1(&reboot_steps), part2(&reboot_steps)) == naive_lit_cubes(*center, steps)
+            },
+        );
+    }
 }
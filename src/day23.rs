@@ -1,24 +1,40 @@
 use crate::day23::Amphipod::*;
-use aoc_runner_derive::aoc;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use crate::error::ParseError;
+use crate::search::dijkstra;
+use crate::solver::Solver;
+use crate::text::normalize;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+const DAY: u8 = 23;
+const SIDE_ROOM_COLUMNS: [usize; 4] = [3, 5, 7, 9];
+const UNFOLDED_ROWS: [[Amphipod; 4]; 2] = [
+    [Desert, Copper, Bronze, Amber],
+    [Desert, Bronze, Amber, Copper],
+];
 
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
-enum Amphipod {
+pub enum Amphipod {
     Amber,
     Bronze,
     Copper,
     Desert,
 }
 
-impl From<char> for Amphipod {
-    fn from(c: char) -> Self {
-        match c {
-            'A' => Amber,
-            'B' => Bronze,
-            'C' => Copper,
-            'D' => Desert,
-            _ => unreachable!(),
+impl TryFrom<&str> for Amphipod {
+    type Error = ParseError;
+
+    fn try_from(amphipod: &str) -> Result<Self, Self::Error> {
+        match amphipod {
+            "A" => Ok(Amber),
+            "B" => Ok(Bronze),
+            "C" => Ok(Copper),
+            "D" => Ok(Desert),
+            _ => Err(ParseError::new(
+                DAY,
+                amphipod,
+                amphipod,
+                "expected an amphipod",
+            )),
         }
     }
 }
@@ -43,35 +59,53 @@ impl Amphipod {
     }
 }
 
-type SideRoom = Vec<Amphipod>;
+pub type SideRoom = Vec<Amphipod>;
 
-type SideRooms = [SideRoom; 4];
+pub type SideRooms = [SideRoom; 4];
 type Hallway = [Option<Amphipod>; 11];
 
-fn parse_input(diagram: &str) -> SideRooms {
-    let mut amphipods: HashMap<(usize, usize), Amphipod> = HashMap::new();
+#[aoc_generator(day23)]
+fn parse_input(diagram: &str) -> Result<SideRooms, ParseError> {
+    let diagram: &str = &normalize(diagram);
 
-    diagram.lines().enumerate().for_each(|(y, line)| {
-        line.chars().enumerate().for_each(|(x, c)| match c {
-            'A' | 'B' | 'C' | 'D' => {
-                amphipods.insert((x, y), c.into());
-            }
-            _ => {}
-        })
-    });
+    let lines = diagram.lines().collect::<Vec<_>>();
 
-    let diagram_height = diagram.lines().count();
+    if lines.len() < 5 {
+        return Err(ParseError::new(
+            DAY,
+            diagram,
+            diagram,
+            "diagram is too short",
+        ));
+    }
 
     let mut side_rooms = [
-        SideRoom::with_capacity(diagram_height - 3),
-        SideRoom::with_capacity(diagram_height - 3),
-        SideRoom::with_capacity(diagram_height - 3),
-        SideRoom::with_capacity(diagram_height - 3),
+        SideRoom::with_capacity(lines.len() - 3),
+        SideRoom::with_capacity(lines.len() - 3),
+        SideRoom::with_capacity(lines.len() - 3),
+        SideRoom::with_capacity(lines.len() - 3),
     ];
 
-    for x in [3, 5, 7, 9] {
-        for y in (2..diagram_height - 1).rev() {
-            side_rooms[(x - 3) / 2].push(amphipods.remove(&(x, y)).unwrap());
+    for (room_index, x) in SIDE_ROOM_COLUMNS.into_iter().enumerate() {
+        for line in lines[2..lines.len() - 1].iter().rev() {
+            let amphipod = line
+                .get(x..x + 1)
+                .ok_or_else(|| ParseError::new(DAY, diagram, line, "missing amphipod"))?;
+
+            side_rooms[room_index]
+                .push(Amphipod::try_from(amphipod).map_err(|e| e.within(diagram, amphipod))?);
+        }
+    }
+
+    Ok(side_rooms)
+}
+
+fn unfold(side_rooms: &SideRooms) -> SideRooms {
+    let mut side_rooms = side_rooms.to_owned();
+
+    for (room_index, side_room) in side_rooms.iter_mut().enumerate() {
+        for row in UNFOLDED_ROWS {
+            side_room.insert(1, row[room_index]);
         }
     }
 
@@ -201,24 +235,6 @@ impl Configuration {
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    configuration: Configuration,
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
 fn organization_cost(side_rooms: &SideRooms) -> Option<usize> {
     let configuration = Configuration {
         side_rooms: side_rooms.to_owned(),
@@ -226,83 +242,45 @@ fn organization_cost(side_rooms: &SideRooms) -> Option<usize> {
         room_capacity: side_rooms[0].len(),
     };
 
-    let mut heap = BinaryHeap::new();
-    let mut energy_to: HashMap<Configuration, usize> = HashMap::new();
-
-    energy_to.insert(configuration.to_owned(), 0);
-
-    heap.push(State {
-        cost: 0,
-        configuration,
-    });
-
-    while let Some(State {
-        cost,
+    dijkstra(
         configuration,
-    }) = heap.pop()
-    {
-        if configuration.is_final() {
-            return Some(cost);
-        }
-
-        if let Some(old_cost) = energy_to.get(&configuration) {
-            if cost > *old_cost {
-                continue;
-            }
-        }
-
-        for (next_configuration, extra_cost) in configuration.adjacent_configurations() {
-            let next = State {
-                cost: cost + extra_cost,
-                configuration: next_configuration,
-            };
-
-            if next.cost
-                < *energy_to
-                    .get(&next.configuration)
-                    .unwrap_or(&(next.cost + 1))
-            {
-                energy_to.insert(next.configuration.clone(), next.cost);
-                heap.push(next);
-            }
-        }
-    }
-
-    None
+        Configuration::adjacent_configurations,
+        Configuration::is_final,
+    )
+    .map(|path| path.cost)
 }
 
 #[aoc(day23, part1)]
-fn part1(diagram: &str) -> Option<usize> {
-    let side_rooms = parse_input(diagram);
-    organization_cost(&side_rooms)
+fn part1(side_rooms: &SideRooms) -> Option<usize> {
+    organization_cost(side_rooms)
 }
 
-fn unfold(diagram: &str) -> String {
-    let mut lines = diagram.lines();
-    let mut unfolded_diagram = String::new();
+#[aoc(day23, part2)]
+fn part2(side_rooms: &SideRooms) -> Option<usize> {
+    organization_cost(&unfold(side_rooms))
+}
 
-    (0..3).for_each(|_| {
-        unfolded_diagram.push_str(lines.next().unwrap());
-        unfolded_diagram.push('\n');
-    });
+/// Day 23: Amphipod.
+pub struct Day23;
 
-    unfolded_diagram.push_str(
-        r"  #D#C#B#A#
-  #D#B#A#C#",
-    );
+impl Solver for Day23 {
+    const DAY: u8 = DAY;
 
-    lines.for_each(|l| {
-        unfolded_diagram.push('\n');
-        unfolded_diagram.push_str(l);
-    });
+    type Input = SideRooms;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
 
-    unfolded_diagram
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-#[aoc(day23, part2)]
-fn part2(diagram: &str) -> Option<usize> {
-    let side_rooms = parse_input(&unfold(diagram));
-    organization_cost(&side_rooms)
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -317,11 +295,11 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(TEST_INPUT), Some(12_521));
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), Some(12_521));
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(TEST_INPUT), Some(44_169));
+        assert_eq!(part2(&parse_input(TEST_INPUT).unwrap()), Some(44_169));
     }
 }
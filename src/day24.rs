@@ -1,6 +1,10 @@
 use crate::day24::ModelNumberType::{Largest, Smallest};
+use crate::error::{parse_number, ParseError};
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 
+const DAY: u8 = 24;
 const NUM_OF_INPUTS: usize = 14;
 const BLOCK_LEN: usize = 18;
 const DIV_Z_POSITION: usize = 4;
@@ -8,41 +12,65 @@ const ADD_X_POSITION: usize = 5;
 const ADD_Y_POSITION: usize = 15;
 
 #[derive(Debug)]
-struct Block {
+pub struct Block {
     div_z: i64,
     add_x: i64,
     add_y: i64,
 }
 
+fn parse_operand(input: &str, instruction: &str, expected: &str) -> Result<i64, ParseError> {
+    let operand = instruction.strip_prefix(expected).ok_or_else(|| {
+        ParseError::new(DAY, input, instruction, format!("expected `{}`", expected))
+    })?;
+
+    parse_number(DAY, input, operand)
+}
+
 #[aoc_generator(day24)]
-fn parse_input(input: &str) -> Vec<Block> {
+fn parse_input(input: &str) -> Result<Vec<Block>, ParseError> {
+    let input: &str = &normalize(input);
+
     let mut blocks = Vec::with_capacity(NUM_OF_INPUTS);
     let instructions = input.lines().collect::<Vec<_>>();
 
+    if instructions.len() != NUM_OF_INPUTS * BLOCK_LEN {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            instructions.last().unwrap_or(&input),
+            format!("expected {} instructions", NUM_OF_INPUTS * BLOCK_LEN),
+        ));
+    }
+
     for i in 0..NUM_OF_INPUTS {
+        let div_z_instruction = instructions[i * BLOCK_LEN + DIV_Z_POSITION];
+
         blocks.push(Block {
-            div_z: instructions[i * BLOCK_LEN + DIV_Z_POSITION]
-                .split_whitespace()
-                .last()
-                .unwrap()
-                .parse()
-                .unwrap(),
-            add_x: instructions[i * BLOCK_LEN + ADD_X_POSITION]
-                .split_whitespace()
-                .last()
-                .unwrap()
-                .parse()
-                .unwrap(),
-            add_y: instructions[i * BLOCK_LEN + ADD_Y_POSITION]
-                .split_whitespace()
-                .last()
-                .unwrap()
-                .parse()
-                .unwrap(),
+            div_z: match parse_operand(input, div_z_instruction, "div z ")? {
+                div_z @ (1 | 26) => div_z,
+                _ => {
+                    return Err(ParseError::new(
+                        DAY,
+                        input,
+                        div_z_instruction,
+                        "expected `div z 1` or `div z 26`",
+                    ))
+                }
+            },
+            add_x: parse_operand(
+                input,
+                instructions[i * BLOCK_LEN + ADD_X_POSITION],
+                "add x ",
+            )?,
+            add_y: parse_operand(
+                input,
+                instructions[i * BLOCK_LEN + ADD_Y_POSITION],
+                "add y ",
+            )?,
         })
     }
 
-    blocks
+    Ok(blocks)
 }
 
 struct Node {
@@ -63,9 +91,7 @@ fn find_model_number_by_type(blocks: &[Block], model_number_type: ModelNumberTyp
         total_input: 0,
     }];
 
-    while !nodes.is_empty() {
-        let node = nodes.pop().unwrap();
-
+    while let Some(node) = nodes.pop() {
         if node.index == NUM_OF_INPUTS {
             if node.z_input == 0 {
                 return Some(node.total_input);
@@ -110,3 +136,26 @@ fn part1(blocks: &[Block]) -> Option<i64> {
 fn part2(blocks: &[Block]) -> Option<i64> {
     find_model_number_by_type(blocks, Smallest)
 }
+
+/// Day 24: Arithmetic Logic Unit.
+pub struct Day24;
+
+impl Solver for Day24 {
+    const DAY: u8 = DAY;
+
+    type Input = Vec<Block>;
+    type Part1 = Option<i64>;
+    type Part2 = Option<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        part2(input)
+    }
+}
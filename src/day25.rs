@@ -1,127 +1,133 @@
 use crate::day25::Cucumber::{EastFacing, SouthFacing};
+use crate::day25::Tile::{Empty, Occupied};
+use crate::error::ParseError;
+use crate::frames::{Frames, Render, Rgb};
+use crate::grid::Grid;
+use crate::solver::Solver;
+use crate::text::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
-use std::collections::HashMap;
-use std::fmt::{Debug, Formatter};
-use std::ops::Add;
+use std::fmt::{Display, Formatter};
 
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
+const DAY: u8 = 25;
+
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 enum Cucumber {
     EastFacing,
     SouthFacing,
 }
 
-impl From<char> for Cucumber {
-    fn from(cucumber: char) -> Self {
-        match cucumber {
-            '>' => EastFacing,
-            'v' => SouthFacing,
-            _ => unreachable!(),
+impl Display for Cucumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EastFacing => write!(f, ">"),
+            SouthFacing => write!(f, "v"),
         }
     }
 }
 
-impl Debug for Cucumber {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                EastFacing => ">",
-                SouthFacing => "v",
-            }
-        )
+impl From<Cucumber> for (i8, i8) {
+    fn from(cucumber: Cucumber) -> Self {
+        match cucumber {
+            EastFacing => (1, 0),
+            SouthFacing => (0, 1),
+        }
     }
 }
 
 #[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
-struct Vec2 {
-    x: usize,
-    y: usize,
+enum Tile {
+    Empty,
+    Occupied(Cucumber),
 }
 
-type Position = Vec2;
-
-impl From<(usize, usize)> for Vec2 {
-    fn from((x, y): (usize, usize)) -> Self {
-        Self { x, y }
+impl Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Empty),
+            '>' => Some(Occupied(EastFacing)),
+            'v' => Some(Occupied(SouthFacing)),
+            _ => None,
+        }
     }
 }
 
-impl From<Vec2> for (usize, usize) {
-    fn from(vec2: Vec2) -> Self {
-        (vec2.x, vec2.y)
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Empty => write!(f, "."),
+            Occupied(cucumber) => write!(f, "{}", cucumber),
+        }
     }
 }
 
-impl From<Cucumber> for Vec2 {
-    fn from(cucumber: Cucumber) -> Self {
-        match cucumber {
-            EastFacing => (1, 0).into(),
-            SouthFacing => (0, 1).into(),
+impl Render for Tile {
+    fn glyph(&self) -> char {
+        match self {
+            Empty => '.',
+            Occupied(EastFacing) => '>',
+            Occupied(SouthFacing) => 'v',
+        }
+    }
+
+    fn color(&self) -> Rgb {
+        match self {
+            Empty => [0, 32, 64],
+            Occupied(EastFacing) => [255, 128, 0],
+            Occupied(SouthFacing) => [0, 192, 96],
         }
     }
 }
 
-impl Add for Vec2 {
-    type Output = Self;
+#[derive(Clone, Debug)]
+pub struct Map(Grid<Tile>);
 
-    fn add(self, rhs: Self) -> Self {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
+impl Display for Map {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
-#[derive(Clone)]
-struct Map {
-    cucumbers_positions: HashMap<Position, Cucumber>,
-    width: usize,
-    height: usize,
+impl TryFrom<&str> for Map {
+    type Error = ParseError;
+
+    fn try_from(map: &str) -> Result<Self, Self::Error> {
+        Ok(Map(Grid::parse(
+            DAY,
+            map,
+            Tile::from_char,
+            "expected a sea cucumber",
+        )?
+        .with_wrapping(true)))
+    }
 }
 
-impl Debug for Map {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            writeln!(f)?;
-
-            for x in 0..self.width {
-                if self.cucumbers_positions.contains_key(&((x, y).into())) {
-                    write!(
-                        f,
-                        "{:?}",
-                        self.cucumbers_positions.get(&((x, y).into())).unwrap()
-                    )?;
-                } else {
-                    write!(f, ".")?;
-                };
+impl Map {
+    /// Snapshots of the sea floor before the first step and after each step, until no
+    /// sea cucumber moves or `max_steps` steps are taken.
+    pub fn record(&self, max_steps: usize) -> Frames {
+        let mut map = self.to_owned();
+        let mut frames = Frames::default();
+
+        frames.capture(&map.0);
+
+        for _ in 0..max_steps {
+            if !map.step() {
+                break;
             }
+
+            frames.capture(&map.0);
         }
 
-        Ok(())
+        frames
     }
-}
 
-impl From<&str> for Map {
-    fn from(map: &str) -> Self {
-        Map {
-            cucumbers_positions: map
-                .lines()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .filter(|(_, c)| *c != '.')
-                        .map(move |(x, c)| ((x, y).into(), c.into()))
-                })
-                .collect(),
-            width: map.lines().next().unwrap().len(),
-            height: map.lines().count(),
-        }
+    /// The first step on which no sea cucumber moves, if it is at most `max_steps`.
+    pub fn settles_within(&self, max_steps: usize) -> Option<usize> {
+        let mut map = self.to_owned();
+
+        (1..=max_steps).find(|_| !map.step())
     }
-}
 
-impl Map {
     fn stabilize(&mut self) -> usize {
         let mut steps = 1;
 
@@ -134,39 +140,23 @@ impl Map {
 
     fn move_cucumbers(&mut self, cucumber_type: Cucumber) -> bool {
         let mut moved = false;
+        let mut new_map = self.0.clone();
 
-        let mut new_cucumbers_positions = HashMap::with_capacity(self.cucumbers_positions.len());
-
-        for (position, cucumber) in self
-            .cucumbers_positions
-            .iter()
-            .filter(|(_, cucumber)| **cucumber != cucumber_type)
-        {
-            new_cucumbers_positions.insert(*position, cucumber.to_owned());
-        }
-
-        for (position, cucumber) in self
-            .cucumbers_positions
+        for (position, _) in self
+            .0
             .iter()
-            .filter(|(_, cucumber)| **cucumber == cucumber_type)
+            .filter(|(_, tile)| **tile == Occupied(cucumber_type))
         {
-            let mut new_position = *position + cucumber_type.into();
+            let new_position = self.0.step(position, cucumber_type.into()).unwrap();
 
-            if cucumber_type == EastFacing {
-                new_position.x %= self.width;
-            } else {
-                new_position.y %= self.height;
-            }
-
-            if !self.cucumbers_positions.contains_key(&new_position) {
-                new_cucumbers_positions.insert(new_position, cucumber.to_owned());
+            if self.0[new_position] == Empty {
+                new_map[new_position] = Occupied(cucumber_type);
+                new_map[position] = Empty;
                 moved = true;
-            } else {
-                new_cucumbers_positions.insert(*position, cucumber.to_owned());
             }
         }
 
-        self.cucumbers_positions = new_cucumbers_positions;
+        self.0 = new_map;
 
         moved
     }
@@ -180,8 +170,10 @@ impl Map {
 }
 
 #[aoc_generator(day25)]
-fn parse_input(input: &str) -> Map {
-    input.into()
+fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let input: &str = &normalize(input);
+
+    input.try_into()
 }
 
 #[aoc(day25, part1)]
@@ -191,6 +183,31 @@ fn part1(map: &Map) -> usize {
     map.stabilize()
 }
 
+/// Day 25: Sea Cucumber.
+pub struct Day25;
+
+impl Solver for Day25 {
+    const DAY: u8 = DAY;
+    const PARTS: u8 = 1;
+
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = Option<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    /// The last day has no second puzzle.
+    fn part2(_: &Self::Input) -> Self::Part2 {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,6 +224,23 @@ v.v..>>v.v
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse_input(TEST_INPUT)), 58);
+        assert_eq!(part1(&parse_input(TEST_INPUT).unwrap()), 58);
+    }
+
+    #[test]
+    fn record_example() {
+        let frames = parse_input(TEST_INPUT).unwrap().record(100);
+
+        assert_eq!(frames.len(), 58);
+        assert!(frames.to_ascii().starts_with("Step 0:\nv...>>.vv>"));
+    }
+
+    #[test]
+    fn settles_within() {
+        let map = parse_input(TEST_INPUT).unwrap();
+
+        assert_eq!(map.settles_within(100), Some(58));
+        assert_eq!(map.settles_within(57), None);
+        assert_eq!(parse_input("..>").unwrap().settles_within(100), None);
     }
 }
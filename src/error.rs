@@ -0,0 +1,192 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    day: u8,
+    line: usize,
+    column: usize,
+    text: String,
+    message: String,
+}
+
+impl ParseError {
+    /// Creates an error pointing at `text`, which is expected to be a slice of `source`.
+    /// Line and column are 1-based and derived from the position of `text` inside `source`;
+    /// if `text` does not borrow from `source` the error points at the start of `source`.
+    pub fn new(day: u8, source: &str, text: &str, message: impl Into<String>) -> Self {
+        let (line, column) = position(source, text);
+
+        Self {
+            day,
+            line,
+            column,
+            text: text.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    /// Re-anchors an error produced while parsing `part` so that it points into `source`.
+    pub fn within(mut self, source: &str, part: &str) -> Self {
+        let (line, column) = position(source, part);
+
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+
+        self.line += line - 1;
+
+        self
+    }
+
+    /// Moves an error produced while parsing a single line to line `line` of its input.
+    pub(crate) fn on_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+
+        self
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {} `{}`",
+            self.day, self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunError {
+    UnknownSolution {
+        day: u8,
+        part: u8,
+        variant: Option<String>,
+    },
+    Parse(ParseError),
+    NoAnswer,
+    /// The solution panicked with the given message.
+    Panicked(String),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::UnknownSolution {
+                day,
+                part,
+                variant: None,
+            } => write!(f, "no solution for day {} part {}", day, part),
+            RunError::UnknownSolution {
+                day,
+                part,
+                variant: Some(variant),
+            } => write!(
+                f,
+                "no solution for day {} part {} named `{}`",
+                day, part, variant
+            ),
+            RunError::Parse(error) => write!(f, "{}", error),
+            RunError::NoAnswer => write!(f, "the input has no answer"),
+            RunError::Panicked(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<ParseError> for RunError {
+    fn from(error: ParseError) -> Self {
+        RunError::Parse(error)
+    }
+}
+
+fn position(source: &str, text: &str) -> (usize, usize) {
+    let start = source.as_ptr() as usize;
+    let offset = (text.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| *offset <= source.len())
+        .unwrap_or(0);
+
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+
+    (line, column)
+}
+
+/// Parses `text`, a slice of `source`, reporting its position on failure.
+pub(crate) fn parse_number<T: FromStr>(day: u8, source: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(day, source, text, "invalid number"))
+}
+
+/// Returns the character of `text` starting at byte `index` as a slice.
+pub(crate) fn char_at(text: &str, index: usize) -> &str {
+    let len = text[index..].chars().next().map_or(0, char::len_utf8);
+
+    &text[index..index + len]
+}
+
+/// Returns the next item of `iter`, or an error pointing at `source` if it is exhausted.
+pub(crate) fn next_part<'a>(
+    day: u8,
+    source: &str,
+    iter: &mut impl Iterator<Item = &'a str>,
+    what: &str,
+) -> Result<&'a str, ParseError> {
+    iter.next()
+        .ok_or_else(|| ParseError::new(day, source, source, format!("missing {}", what)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SOURCE: &str = "forward 5\ndown x\nup 3";
+
+    #[test]
+    fn position_of_slice() {
+        let line = SOURCE.lines().nth(1).unwrap();
+        let error = ParseError::new(2, SOURCE, &line[5..], "invalid number");
+
+        assert_eq!((error.line(), error.column(), error.text()), (2, 6, "x"));
+    }
+
+    #[test]
+    fn within_enclosing_source() {
+        let line = SOURCE.lines().nth(1).unwrap();
+        let error = ParseError::new(2, line, &line[5..], "invalid number").within(SOURCE, line);
+
+        assert_eq!((error.line(), error.column()), (2, 6));
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 2, column 6: invalid number `x`"
+        );
+    }
+}
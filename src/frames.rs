@@ -0,0 +1,403 @@
+//! Step-by-step snapshots of the grid simulations, exportable as text or images.
+
+use crate::day11::Day11;
+use crate::day20::Day20;
+use crate::day25::Day25;
+use crate::error::ParseError;
+use crate::grid::{Grid, Position};
+use crate::solver::Solver;
+use std::collections::HashMap;
+use std::io::{self, ErrorKind, Write};
+
+pub type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [0, 0, 0];
+const GIF_MAX_CODE: u16 = 4095;
+
+/// A grid cell that can be drawn.
+pub trait Render {
+    fn glyph(&self) -> char;
+
+    fn color(&self) -> Rgb;
+}
+
+/// Rendered snapshots of a grid, one per step of a simulation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frames(Vec<Grid<(char, Rgb)>>);
+
+impl Frames {
+    pub fn capture<T: Render>(&mut self, grid: &Grid<T>) {
+        self.0.push(Grid::from_fn(grid.width(), grid.height(), |p| {
+            (grid[p].glyph(), grid[p].color())
+        }));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Every frame as text, headed by its step number and separated by empty lines.
+    pub fn to_ascii(&self) -> String {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(step, frame)| {
+                let glyphs = Grid::from_fn(frame.width(), frame.height(), |p| frame[p].0);
+                format!("Step {}:\n{}", step, glyphs)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Writes a frame as a binary grayscale PGM image, each cell `scale` pixels wide.
+    pub fn write_pgm(&self, step: usize, scale: usize, out: &mut impl Write) -> io::Result<()> {
+        let frame = &self.0[step];
+
+        write!(
+            out,
+            "P5\n{} {}\n255\n",
+            frame.width() * scale,
+            frame.height() * scale
+        )?;
+        out.write_all(&scaled(frame, scale, |(_, [r, g, b])| {
+            vec![((*r as u32 * 299 + *g as u32 * 587 + *b as u32 * 114) / 1000) as u8]
+        }))
+    }
+
+    /// Writes a frame as a binary color PPM image, each cell `scale` pixels wide.
+    pub fn write_ppm(&self, step: usize, scale: usize, out: &mut impl Write) -> io::Result<()> {
+        let frame = &self.0[step];
+
+        write!(
+            out,
+            "P6\n{} {}\n255\n",
+            frame.width() * scale,
+            frame.height() * scale
+        )?;
+        out.write_all(&scaled(frame, scale, |(_, color)| color.to_vec()))
+    }
+
+    /// Writes all frames as a looping animated GIF showing each frame for `delay`
+    /// hundredths of a second. Frames smaller than the largest one are centered on a
+    /// black background. Fails if the frames use more than 256 colors.
+    pub fn write_gif(&self, scale: usize, delay: u16, out: &mut impl Write) -> io::Result<()> {
+        let width = self.0.iter().map(|f| f.width()).max().unwrap_or(0);
+        let height = self.0.iter().map(|f| f.height()).max().unwrap_or(0);
+        let (screen_width, screen_height) = (dimension(width * scale)?, dimension(height * scale)?);
+
+        let mut palette = vec![BACKGROUND];
+        let mut indices = HashMap::from([(BACKGROUND, 0)]);
+
+        for (_, color) in self.0.iter().flat_map(|frame| frame.values()) {
+            if !indices.contains_key(color) {
+                indices.insert(*color, palette.len() as u8);
+                palette.push(*color);
+            }
+        }
+
+        if palette.len() > 256 {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "a GIF cannot have more than 256 colors",
+            ));
+        }
+
+        let table_bits = (1..=8).find(|bits| palette.len() <= 1 << bits).unwrap();
+        palette.resize(1 << table_bits, BACKGROUND);
+
+        out.write_all(b"GIF89a")?;
+        out.write_all(&screen_width.to_le_bytes())?;
+        out.write_all(&screen_height.to_le_bytes())?;
+        out.write_all(&[0xf0 | (table_bits - 1), 0, 0])?;
+        out.write_all(&palette.concat())?;
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        for frame in self.0.iter() {
+            let offset = Position::new((width - frame.width()) / 2, (height - frame.height()) / 2);
+            let canvas = Grid::from_fn(width, height, |p| {
+                p.x.checked_sub(offset.x)
+                    .zip(p.y.checked_sub(offset.y))
+                    .and_then(|(x, y)| frame.get(Position::new(x, y)))
+                    .map_or(0, |(_, color)| indices[color])
+            });
+            let pixels = scaled(&canvas, scale, |index| vec![*index]);
+            let min_code_size = table_bits.max(2);
+
+            out.write_all(&[0x21, 0xf9, 0x04, 0x04])?;
+            out.write_all(&delay.to_le_bytes())?;
+            out.write_all(&[0, 0, 0x2c, 0, 0, 0, 0])?;
+            out.write_all(&screen_width.to_le_bytes())?;
+            out.write_all(&screen_height.to_le_bytes())?;
+            out.write_all(&[0, min_code_size])?;
+
+            for block in lzw_compress(min_code_size, &pixels).chunks(255) {
+                out.write_all(&[block.len() as u8])?;
+                out.write_all(block)?;
+            }
+
+            out.write_all(&[0])?;
+        }
+
+        out.write_all(&[0x3b])
+    }
+}
+
+fn dimension(size: usize) -> io::Result<u16> {
+    size.try_into()
+        .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "the image is too large"))
+}
+
+/// Row-major pixel data of `grid` with each cell drawn as a `scale` x `scale` square.
+fn scaled<T>(grid: &Grid<T>, scale: usize, pixel: impl Fn(&T) -> Vec<u8>) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for y in 0..grid.height() * scale {
+        for x in 0..grid.width() * scale {
+            data.extend(pixel(&grid[Position::new(x / scale, y / scale)]));
+        }
+    }
+
+    data
+}
+
+/// Variable-width LZW as used by GIF, with codes packed least significant bit first.
+fn lzw_compress(min_code_size: u8, indices: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut bytes = Vec::new();
+    let (mut buffer, mut buffered) = (0u32, 0);
+    let mut write = |code: u16, width: u8| {
+        buffer |= (code as u32) << buffered;
+        buffered += width;
+
+        while buffered >= 8 {
+            bytes.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    };
+
+    let mut codes = HashMap::new();
+    let mut next_code = end + 1;
+    let mut width = min_code_size + 1;
+    let mut current: Option<u16> = None;
+
+    write(clear, width);
+
+    for &index in indices {
+        let prefix = match current {
+            Some(prefix) => prefix,
+            None => {
+                current = Some(index as u16);
+                continue;
+            }
+        };
+
+        if let Some(&code) = codes.get(&(prefix, index)) {
+            current = Some(code);
+            continue;
+        }
+
+        write(prefix, width);
+
+        if next_code == GIF_MAX_CODE {
+            write(clear, width);
+            codes.clear();
+            next_code = end + 1;
+            width = min_code_size + 1;
+        } else {
+            codes.insert((prefix, index), next_code);
+            next_code += 1;
+
+            if next_code > 1 << width && width < 12 {
+                width += 1;
+            }
+        }
+
+        current = Some(index as u16);
+    }
+
+    if let Some(prefix) = current {
+        write(prefix, width);
+
+        // Decoders count the last code as if it had added an entry.
+        if next_code >= 1 << width && width < 12 {
+            width += 1;
+        }
+    }
+
+    write(end, width);
+
+    if buffered > 0 {
+        bytes.push(buffer as u8);
+    }
+
+    bytes
+}
+
+/// Default number of steps to record for each simulated day.
+pub fn default_steps(day: u8) -> Option<usize> {
+    match day {
+        11 => Some(100),
+        20 => Some(50),
+        25 => Some(1000),
+        _ => None,
+    }
+}
+
+/// Records up to `steps` steps of the simulation of `day` on `input`; `None` for days
+/// that are not grid simulations.
+pub fn simulate(day: u8, input: &str, steps: usize) -> Option<Result<Frames, ParseError>> {
+    let input = input.trim_end_matches('\n');
+
+    match day {
+        11 => Some(Day11::parse(input).map(|map| map.record(steps))),
+        20 => Some(Day20::parse(input).map(|processor| processor.record(steps))),
+        25 => Some(Day25::parse(input).map(|map| map.record(steps))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Render for bool {
+        fn glyph(&self) -> char {
+            if *self {
+                '#'
+            } else {
+                '.'
+            }
+        }
+
+        fn color(&self) -> Rgb {
+            if *self {
+                [255, 255, 255]
+            } else {
+                [0, 0, 128]
+            }
+        }
+    }
+
+    fn frames() -> Frames {
+        let mut frames = Frames::default();
+        frames.capture(&Grid::from_fn(3, 2, |p| p.x == p.y));
+        frames.capture(&Grid::from_fn(1, 1, |_| true));
+        frames
+    }
+
+    /// A decoder following the code width rules of giflib.
+    fn lzw_decompress(min_code_size: u8, bytes: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = (0..clear).map(|i| vec![i as u8]).collect();
+        let mut width = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut output = Vec::new();
+        let (mut position, mut next_code) = (0, end + 1);
+
+        loop {
+            let code = (0..width as usize).fold(0, |code, bit| {
+                let bit_position = position + bit;
+                code | (((bytes[bit_position / 8] >> (bit_position % 8)) & 1) as usize) << bit
+            });
+            position += width as usize;
+
+            if code == clear {
+                table.truncate(clear);
+                table.extend([Vec::new(), Vec::new()]);
+                width = min_code_size + 1;
+                previous = None;
+                next_code = end + 1;
+                continue;
+            }
+
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = table[previous].clone();
+                    entry.push(table[previous][0]);
+                    entry
+                }
+                (None, None) => panic!("invalid code"),
+            };
+
+            if let Some(previous) = previous {
+                let mut new_entry = table[previous].clone();
+                new_entry.push(entry[0]);
+                table.push(new_entry);
+            }
+
+            output.extend(&entry);
+            previous = Some(code);
+            next_code += 1;
+
+            if next_code > 1 << width && width < 12 {
+                width += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(frames().to_ascii(), "Step 0:\n#..\n.#.\n\nStep 1:\n#");
+    }
+
+    #[test]
+    fn netpbm() {
+        let mut pgm = Vec::new();
+        frames().write_pgm(1, 2, &mut pgm).unwrap();
+        assert_eq!(pgm, b"P5\n2 2\n255\n\xff\xff\xff\xff");
+
+        let mut ppm = Vec::new();
+        frames().write_ppm(0, 1, &mut ppm).unwrap();
+        assert_eq!(&ppm[..11], b"P6\n3 2\n255\n");
+        assert_eq!(&ppm[11..17], [255, 255, 255, 0, 0, 128]);
+    }
+
+    #[test]
+    fn gif() {
+        let mut gif = Vec::new();
+        frames().write_gif(1, 10, &mut gif).unwrap();
+
+        assert_eq!(&gif[..10], b"GIF89a\x03\x00\x02\x00");
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let mut rng = crate::inputs::Rng::new(0);
+
+        let cases = (0..300)
+            .map(|len| (len, 2))
+            .chain([(20_000, 4), (50_000, 256)]);
+
+        for (len, colors) in cases {
+            let min_code_size = (colors as f64).log2().max(2.0) as u8;
+            let indices = (0..len)
+                .map(|i| {
+                    if i % 7 < 3 {
+                        0
+                    } else {
+                        rng.below(colors) as u8
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                lzw_decompress(min_code_size, &lzw_compress(min_code_size, &indices)),
+                indices
+            );
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use crate::error::{next_part, parse_number, ParseError};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A scalar that can be used as a coordinate of [`Vec2`] and [`Vec3`].
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Absolute difference between two coordinates.
+    fn distance(self, other: Self) -> Self;
+
+    /// Adds a small signed step, returning `None` if the result is not representable.
+    fn step(self, delta: i8) -> Option<Self>;
+}
+
+macro_rules! impl_signed_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn distance(self, other: Self) -> Self {
+                    (self - other).abs()
+                }
+
+                fn step(self, delta: i8) -> Option<Self> {
+                    self.checked_add(delta as Self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_unsigned_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn distance(self, other: Self) -> Self {
+                    self.abs_diff(other)
+                }
+
+                fn step(self, delta: i8) -> Option<Self> {
+                    self.checked_add_signed(delta as _)
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_coordinate!(i8, i16, i32, i64, isize);
+impl_unsigned_coordinate!(u8, u16, u32, u64, usize);
+
+pub(crate) const NEIGHBORS_4: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+pub(crate) const NEIGHBORS_8: [(i8, i8); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+const NEIGHBORS_6: [(i8, i8, i8); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coordinate> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// Moves by a small step, returning `None` if a coordinate would leave the range of `T`.
+    pub fn step(self, (dx, dy): (i8, i8)) -> Option<Self> {
+        Some(Self::new(self.x.step(dx)?, self.y.step(dy)?))
+    }
+
+    /// Orthogonally adjacent positions, skipping those that are not representable.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        NEIGHBORS_4.into_iter().filter_map(move |d| self.step(d))
+    }
+
+    /// Orthogonally and diagonally adjacent positions, in reading order.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        NEIGHBORS_8.into_iter().filter_map(move |d| self.step(d))
+    }
+
+    /// Parses comma-separated coordinates such as `3,-4`, reporting errors for `day`.
+    pub fn from_csv(day: u8, text: &str) -> Result<Self, ParseError>
+    where
+        T: FromStr,
+    {
+        let mut split = text.split(',');
+        let mut next_coordinate =
+            |what| next_part(day, text, &mut split, what).and_then(|c| parse_number(day, text, c));
+
+        Ok(Self::new(next_coordinate("x")?, next_coordinate("y")?))
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Vec2<T> {
+    /// Rotates by a quarter turn counterclockwise, with the y axis pointing up.
+    pub fn rotate_left(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates by a quarter turn clockwise, with the y axis pointing up.
+    pub fn rotate_right(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(vec2: Vec2<T>) -> Self {
+        (vec2.x, vec2.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coordinate> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+
+    /// Moves by a small step, returning `None` if a coordinate would leave the range of `T`.
+    pub fn step(self, (dx, dy, dz): (i8, i8, i8)) -> Option<Self> {
+        Some(Self::new(
+            self.x.step(dx)?,
+            self.y.step(dy)?,
+            self.z.step(dz)?,
+        ))
+    }
+
+    /// Positions sharing a face with this one.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        NEIGHBORS_6.into_iter().filter_map(move |d| self.step(d))
+    }
+
+    /// Positions sharing a face, an edge or a corner with this one.
+    pub fn neighbors26(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter(|d| *d != (0, 0, 0))
+            .filter_map(move |d| self.step(d))
+    }
+
+    /// Parses comma-separated coordinates such as `3,-4,5`, reporting errors for `day`.
+    pub fn from_csv(day: u8, text: &str) -> Result<Self, ParseError>
+    where
+        T: FromStr,
+    {
+        let mut split = text.split(',');
+        let mut next_coordinate =
+            |what| next_part(day, text, &mut split, what).and_then(|c| parse_number(day, text, c));
+
+        Ok(Self::new(
+            next_coordinate("x")?,
+            next_coordinate("y")?,
+            next_coordinate("z")?,
+        ))
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Vec3<T> {
+    /// All 24 orientations of this vector obtained by rotating the coordinate system,
+    /// starting with the identity. The n-th element of the result uses the same
+    /// rotation for every vector.
+    pub fn rotations(self) -> [Self; 24] {
+        let Self { x, y, z } = self;
+
+        [
+            (x, y, z),
+            (x, z, -y),
+            (x, -y, -z),
+            (x, -z, y),
+            (-x, y, -z),
+            (-x, z, y),
+            (-x, -y, z),
+            (-x, -z, -y),
+            (y, x, -z),
+            (y, -x, z),
+            (y, z, x),
+            (y, -z, -x),
+            (-y, x, z),
+            (-y, -x, -z),
+            (-y, z, -x),
+            (-y, -z, x),
+            (z, x, y),
+            (z, -x, -y),
+            (z, y, -x),
+            (z, -y, x),
+            (-z, x, -y),
+            (-z, -x, y),
+            (-z, y, x),
+            (-z, -y, -x),
+        ]
+        .map(|v| v.into())
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Vec3<T>> for (T, T, T) {
+    fn from(vec3: Vec3<T>) -> Self {
+        (vec3.x, vec3.y, vec3.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn distances() {
+        let a = Vec2::new(1, -2);
+        let b = Vec2::new(-3, 5);
+
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(Vec3::new(1usize, 2, 3).manhattan(Vec3::new(3, 2, 1)), 4);
+    }
+
+    #[test]
+    fn neighbors_stay_representable() {
+        assert_eq!(Vec2::new(0usize, 0).neighbors4().count(), 2);
+        assert_eq!(Vec2::new(0usize, 0).neighbors8().count(), 3);
+        assert_eq!(Vec2::new(5, 5).neighbors8().count(), 8);
+        assert_eq!(Vec3::new(0, 0, 0).neighbors6().count(), 6);
+        assert_eq!(Vec3::new(0, 0, 0).neighbors26().count(), 26);
+    }
+
+    #[test]
+    fn rotations_are_distinct_and_closed() {
+        let v = Vec3::new(1, 2, 3);
+        let rotations = v.rotations().into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(
+            Vec2::new(1, 2).rotate_left().rotate_right(),
+            Vec2::new(1, 2)
+        );
+        assert_eq!(
+            Vec2::new(1, 2).rotate_left().rotate_left(),
+            -Vec2::new(1, 2)
+        );
+    }
+}
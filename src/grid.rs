@@ -0,0 +1,237 @@
+use crate::error::{char_at, ParseError};
+use crate::geometry::{Vec2, NEIGHBORS_4, NEIGHBORS_8};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+pub type Position = Vec2<usize>;
+
+/// A dense rectangular grid stored in row-major order.
+///
+/// A wrapping grid is a torus: stepping off one edge re-enters from the opposite one.
+/// Wrapping only affects [`Grid::step`] and the neighbor iterators; [`Grid::get`]
+/// is always bounds-checked.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    wrapping: bool,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Position) -> T) -> Self {
+        Self {
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| Position::new(x, y)))
+                .map(&mut f)
+                .collect(),
+            width,
+            height,
+            wrapping: false,
+        }
+    }
+
+    /// Parses a map with one character per cell, converting each with `parse_cell`.
+    ///
+    /// Errors are positioned in `source` and attributed to puzzle `day`: an empty map,
+    /// rows of different widths, or a character `parse_cell` rejects, which is reported
+    /// with `message`.
+    pub fn parse(
+        day: u8,
+        source: &str,
+        parse_cell: impl Fn(char) -> Option<T>,
+        message: &str,
+    ) -> Result<Self, ParseError> {
+        let width = source.lines().next().map_or(0, |l| l.chars().count());
+
+        if width == 0 {
+            return Err(ParseError::new(day, source, source, "empty map"));
+        }
+
+        if let Some(line) = source.lines().find(|l| l.chars().count() != width) {
+            return Err(ParseError::new(day, source, line, "unexpected row width"));
+        }
+
+        Ok(Self {
+            cells: source
+                .lines()
+                .flat_map(|line| {
+                    line.char_indices().map(|(x, c)| {
+                        parse_cell(c)
+                            .ok_or_else(|| ParseError::new(day, source, char_at(line, x), message))
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            width,
+            height: source.lines().count(),
+            wrapping: false,
+        })
+    }
+
+    pub fn with_wrapping(mut self, wrapping: bool) -> Self {
+        self.wrapping = wrapping;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves from `position` by a small step, wrapping around the edges of a wrapping grid.
+    /// Returns `None` if the step leaves a non-wrapping grid.
+    pub fn step(&self, position: Position, (dx, dy): (i8, i8)) -> Option<Position> {
+        if self.wrapping {
+            Some(Position::new(
+                wrap(position.x, dx, self.width),
+                wrap(position.y, dy, self.height),
+            ))
+        } else {
+            position
+                .step((dx, dy))
+                .filter(|position| self.contains(*position))
+        }
+    }
+
+    /// Orthogonally adjacent positions inside the grid.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBORS_4
+            .into_iter()
+            .filter_map(move |d| self.step(position, d))
+    }
+
+    /// Orthogonally and diagonally adjacent positions inside the grid, in reading order.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBORS_8
+            .into_iter()
+            .filter_map(move |d| self.step(position, d))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.len()).map(move |i| Position::new(i % width, i / width))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Cells with their positions, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y * self.width + position.x)
+    }
+}
+
+fn wrap(coordinate: usize, delta: i8, size: usize) -> usize {
+    (coordinate as isize + delta as isize).rem_euclid(size as isize) as usize
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).expect("position outside of the grid")
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .expect("position outside of the grid")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = r"123
+456";
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(0, input, |c| c.to_digit(10), "invalid digit")
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits(TEST_INPUT).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Position::new(2, 1)], 6);
+        assert_eq!(grid.get(Position::new(3, 0)), None);
+        assert_eq!(grid.to_string(), TEST_INPUT);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = digits("123\n4x6").unwrap_err();
+
+        assert_eq!((error.line(), error.column(), error.text()), (2, 2, "x"));
+        assert_eq!(
+            digits("123\n45").unwrap_err().message(),
+            "unexpected row width"
+        );
+    }
+
+    #[test]
+    fn wrapping_neighbors() {
+        let grid = digits(TEST_INPUT).unwrap();
+
+        assert_eq!(grid.neighbors4(Position::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbors8(Position::new(1, 0)).count(), 5);
+        assert_eq!(
+            grid.with_wrapping(true).step(Position::new(2, 0), (1, -1)),
+            Some(Position::new(0, 1))
+        );
+    }
+}
@@ -0,0 +1,896 @@
+//! Seeded generators of random, well-formed puzzle inputs.
+//!
+//! Every day takes a `size` knob whose meaning is documented on its generator; the
+//! [`DEFAULT_SIZES`] are close to the official inputs. The same seed and size always
+//! produce the same input.
+
+use crate::day11::Day11;
+use crate::day25::Day25;
+use crate::geometry::Vec3;
+use crate::solver::Solver;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Sizes close to those of the official inputs, indexed by day minus one.
+pub const DEFAULT_SIZES: [usize; 25] = [
+    2000, 1000, 1000, 100, 500, 300, 1000, 200, 100, 100, 10, 10, 800, 20, 100, 60, 100, 100, 12,
+    100, 1, 420, 1, 1, 139,
+];
+
+/// SplitMix64, a small deterministic generator that is good enough for test data.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`; `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `min..=max`.
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        min + (self.next_u64() % (max - min + 1) as u64) as i64
+    }
+
+    /// `true` with a probability of `numerator / denominator`.
+    pub fn ratio(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Generates an input for `day`, using the default size of the day if `size` is `None`.
+pub fn generate(day: u8, seed: u64, size: Option<usize>) -> Option<String> {
+    let generator = match day {
+        1 => day01,
+        2 => day02,
+        3 => day03,
+        4 => day04,
+        5 => day05,
+        6 => day06,
+        7 => day07,
+        8 => day08,
+        9 => day09,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        15 => day15,
+        16 => day16,
+        17 => day17,
+        18 => day18,
+        19 => day19,
+        20 => day20,
+        21 => day21,
+        22 => day22,
+        23 => day23,
+        24 => day24,
+        25 => day25,
+        _ => return None,
+    };
+    let size = size.unwrap_or(DEFAULT_SIZES[day as usize - 1]).max(1);
+
+    Some(generator(&mut Rng::new(seed), size))
+}
+
+fn lines(items: impl Iterator<Item = String>) -> String {
+    items.collect::<Vec<_>>().join("\n")
+}
+
+fn digit_map(rng: &mut Rng, width: usize, height: usize, digits: (u8, u8)) -> String {
+    lines((0..height).map(|_| {
+        (0..width)
+            .map(|_| char::from(b'0' + rng.range(digits.0 as i64, digits.1 as i64) as u8))
+            .collect()
+    }))
+}
+
+/// `size` depth measurements.
+pub fn day01(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100, 200);
+
+    lines((0..size).map(|_| {
+        depth = (depth + rng.range(-20, 30)).max(0);
+        depth.to_string()
+    }))
+}
+
+/// `size` commands; the aim never goes above the surface.
+pub fn day02(rng: &mut Rng, size: usize) -> String {
+    const MAX_AIM: i64 = 20;
+
+    let mut aim = 0;
+
+    lines((0..size).map(|_| {
+        let units = rng.range(1, 9);
+
+        match rng.below(3) {
+            0 => format!("forward {}", units),
+            _ if aim + units <= MAX_AIM && (aim < units || rng.ratio(1, 2)) => {
+                aim += units;
+                format!("down {}", units)
+            }
+            _ if aim >= units => {
+                aim -= units;
+                format!("up {}", units)
+            }
+            _ => format!("forward {}", units),
+        }
+    }))
+}
+
+/// `size` distinct diagnostic numbers, at least 12 bits wide.
+pub fn day03(rng: &mut Rng, size: usize) -> String {
+    let width = (usize::BITS - (size * 2).leading_zeros()).clamp(12, 32) as usize;
+    let mut entries = HashSet::new();
+    let mut report = Vec::with_capacity(size);
+
+    while report.len() < size {
+        let entry = rng.next_u64() & ((1 << width) - 1);
+
+        if entries.insert(entry) {
+            report.push(format!("{:0width$b}", entry, width = width));
+        }
+    }
+
+    report.join("\n")
+}
+
+/// `size` 5x5 bingo boards; every number from 0 to 99 is drawn.
+pub fn day04(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = (0..100).collect::<Vec<u8>>();
+
+    rng.shuffle(&mut numbers);
+
+    let mut input = numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        input.push('\n');
+
+        for row in numbers[..25].chunks(5) {
+            input.push('\n');
+            input.push_str(
+                &row.iter()
+                    .map(|n| format!("{:>2}", n))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+    }
+
+    input
+}
+
+/// `size` horizontal, vertical or diagonal lines inside a 1000x1000 area.
+pub fn day05(rng: &mut Rng, size: usize) -> String {
+    const EXTENT: i64 = 1000;
+
+    lines((0..size).map(|_| {
+        let (x1, y1) = (rng.range(0, EXTENT - 1), rng.range(0, EXTENT - 1));
+        let (x2, y2) = match rng.below(3) {
+            0 => ((x1 + rng.range(1, EXTENT - 1)) % EXTENT, y1),
+            1 => (x1, (y1 + rng.range(1, EXTENT - 1)) % EXTENT),
+            _ => {
+                let (dx, dy) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+                let room_x = if dx > 0 { EXTENT - 1 - x1 } else { x1 };
+                let room_y = if dy > 0 { EXTENT - 1 - y1 } else { y1 };
+                let room = room_x.min(room_y);
+
+                if room == 0 {
+                    (x1, (y1 + 1) % EXTENT)
+                } else {
+                    let length = rng.range(1, room);
+                    (x1 + dx * length, y1 + dy * length)
+                }
+            }
+        };
+
+        format!("{},{} -> {},{}", x1, y1, x2, y2)
+    }))
+}
+
+/// `size` lanternfish with timers between 1 and 5.
+pub fn day06(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| rng.range(1, 5).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// `size` crab positions below 2000.
+pub fn day07(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| rng.below(2000).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// `size` displays, each with its own wiring.
+pub fn day08(rng: &mut Rng, size: usize) -> String {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+
+    lines((0..size).map(|_| {
+        let mut wiring = ('a'..='g').collect::<Vec<_>>();
+        rng.shuffle(&mut wiring);
+
+        let pattern = |rng: &mut Rng, digit: &str| {
+            let mut segments = digit
+                .chars()
+                .map(|c| wiring[(c as u8 - b'a') as usize])
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut segments);
+            segments.into_iter().collect::<String>()
+        };
+
+        let mut patterns = DIGITS
+            .iter()
+            .map(|digit| pattern(rng, digit))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut patterns);
+
+        let output = (0..4)
+            .map(|_| {
+                let digit = rng.choose(&DIGITS);
+                pattern(rng, digit)
+            })
+            .collect::<Vec<_>>();
+
+        format!("{} | {}", patterns.join(" "), output.join(" "))
+    }))
+}
+
+/// A `size` x `size` height map.
+pub fn day09(rng: &mut Rng, size: usize) -> String {
+    digit_map(rng, size, size, (0, 9))
+}
+
+/// `size` lines, an odd number of them incomplete and the rest corrupted.
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    const BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+    let incomplete = (size / 2) | 1;
+    let mut kinds = (0..size).map(|i| i < incomplete).collect::<Vec<_>>();
+    rng.shuffle(&mut kinds);
+
+    lines(kinds.into_iter().map(|is_incomplete| {
+        let mut line = String::new();
+        let mut stack = Vec::new();
+
+        for _ in 0..rng.range(20, 110) {
+            if stack.is_empty() || rng.ratio(1, 2) {
+                let (open, close) = *rng.choose(&BRACKETS);
+                line.push(open);
+                stack.push(close);
+            } else {
+                line.push(stack.pop().unwrap());
+            }
+        }
+
+        if stack.is_empty() {
+            let (open, close) = *rng.choose(&BRACKETS);
+            line.push(open);
+            stack.push(close);
+        }
+
+        if !is_incomplete {
+            let expected = *stack.last().unwrap();
+            let wrong = BRACKETS
+                .iter()
+                .map(|(_, close)| *close)
+                .filter(|close| *close != expected)
+                .collect::<Vec<_>>();
+
+            line.push(*rng.choose(&wrong));
+        }
+
+        line
+    }))
+}
+
+/// A `size` x `size` grid of octopuses that all flash together within 1000 steps.
+/// Random grids larger than the official 10x10 rarely do, so those, and small grids
+/// after a number of failed attempts, are built from two adjacent energy levels: when
+/// the higher ones flash, every lower one is one short and flashes in the cascade.
+pub fn day11(rng: &mut Rng, size: usize) -> String {
+    const MAX_STEPS: usize = 1000;
+    const ATTEMPTS: usize = 100;
+    const MAX_RANDOM_SIZE: usize = 10;
+
+    if size <= MAX_RANDOM_SIZE {
+        for _ in 0..ATTEMPTS {
+            let input = digit_map(rng, size, size, (0, 9));
+            let mut map = Day11::parse(&input).unwrap();
+
+            if (0..MAX_STEPS).any(|_| map.flashes_after_step() == size * size) {
+                return input;
+            }
+        }
+    }
+
+    let high = rng.range(1, 9) as u8;
+    let mut input = digit_map(rng, size, size, (high - 1, high)).into_bytes();
+    if let Some(first) = input.first_mut() {
+        *first = b'0' + high;
+    }
+
+    String::from_utf8(input).unwrap()
+}
+
+/// `size` small caves and a third as many big ones; big caves are never adjacent,
+/// so the number of paths stays finite but grows very quickly with `size`.
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let mut names = HashSet::new();
+    let mut name = |rng: &mut Rng, base: u8| loop {
+        let name = (0..2)
+            .map(|_| char::from(base + rng.below(26) as u8))
+            .collect::<String>();
+
+        if name != "st" && names.insert(name.to_owned()) {
+            return name;
+        }
+    };
+
+    let small = (0..size).map(|_| name(rng, b'a')).collect::<Vec<_>>();
+    let big = (0..(size / 3).max(1))
+        .map(|_| name(rng, b'A'))
+        .collect::<Vec<_>>();
+    let not_big = ["start".to_owned(), "end".to_owned()]
+        .into_iter()
+        .chain(small.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let mut edges = Vec::new();
+    let mut add_edge = |a: &String, b: &String| {
+        if a != b
+            && !edges
+                .iter()
+                .any(|(x, y)| (x == a && y == b) || (x == b && y == a))
+        {
+            edges.push((a.to_owned(), b.to_owned()));
+        }
+    };
+
+    add_edge(&not_big[0], rng.choose(&small));
+    add_edge(&not_big[1], rng.choose(&small));
+
+    for cave in small.iter() {
+        for _ in 0..rng.range(1, 2) {
+            add_edge(cave, rng.choose(&not_big));
+        }
+    }
+
+    for cave in big.iter() {
+        for _ in 0..rng.range(2, 3) {
+            add_edge(cave, rng.choose(&not_big));
+        }
+    }
+
+    rng.shuffle(&mut edges);
+
+    lines(edges.into_iter().map(|(a, b)| format!("{}-{}", a, b)))
+}
+
+/// `size` dots folding down to a 40x6 sheet in 12 folds.
+pub fn day13(rng: &mut Rng, size: usize) -> String {
+    const FOLDS: usize = 12;
+
+    let (mut width, mut height) = (40, 6);
+    let mut reversed_folds = Vec::with_capacity(FOLDS);
+
+    for _ in 0..FOLDS {
+        if rng.ratio(1, 2) {
+            reversed_folds.push(('x', width));
+            width = width * 2 + 1;
+        } else {
+            reversed_folds.push(('y', height));
+            height = height * 2 + 1;
+        }
+    }
+
+    let dots = (0..size).map(|_| {
+        let (mut x, mut y) = (rng.below(40), rng.below(6));
+
+        for (axis, line) in reversed_folds.iter() {
+            if rng.ratio(1, 2) {
+                match axis {
+                    'x' => x = 2 * line - x,
+                    _ => y = 2 * line - y,
+                }
+            }
+        }
+
+        format!("{},{}", x, y)
+    });
+
+    format!(
+        "{}\n\n{}",
+        lines(dots),
+        lines(
+            reversed_folds
+                .iter()
+                .rev()
+                .map(|(axis, line)| format!("fold along {}={}", axis, line))
+        )
+    )
+}
+
+/// A template of `size` elements and a rule for every pair of ten elements.
+pub fn day14(rng: &mut Rng, size: usize) -> String {
+    const ELEMENTS: [char; 10] = ['B', 'C', 'F', 'H', 'K', 'N', 'O', 'P', 'S', 'V'];
+
+    let template = (0..size.max(2))
+        .map(|_| *rng.choose(&ELEMENTS))
+        .collect::<String>();
+    let rules = ELEMENTS
+        .iter()
+        .flat_map(|a| ELEMENTS.iter().map(move |b| (*a, *b)));
+    let rules = rules
+        .map(|(a, b)| format!("{}{} -> {}", a, b, rng.choose(&ELEMENTS)))
+        .collect::<Vec<_>>();
+
+    format!("{}\n\n{}", template, rules.join("\n"))
+}
+
+/// A `size` x `size` risk level map.
+pub fn day15(rng: &mut Rng, size: usize) -> String {
+    digit_map(rng, size, size, (1, 9))
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u64, len: usize) {
+    bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+}
+
+/// Appends a random packet of at most `budget` packets and returns its value.
+fn bits_packet(rng: &mut Rng, bits: &mut Vec<bool>, budget: usize) -> u64 {
+    const LITERAL: u64 = 4;
+    const MAX_PRODUCT: u64 = 1 << 32;
+
+    push_bits(bits, rng.below(8) as u64, 3);
+
+    if budget <= 1 || rng.ratio(1, 4) {
+        let value = rng.below(1 << 12) as u64;
+        let groups = (0..4).rev().find(|i| value >> (i * 4) != 0).unwrap_or(0);
+
+        push_bits(bits, LITERAL, 3);
+
+        for group in (0..=groups).rev() {
+            push_bits(bits, (group > 0) as u64, 1);
+            push_bits(bits, (value >> (group * 4)) & 0xf, 4);
+        }
+
+        return value;
+    }
+
+    let mut type_id = *rng.choose(&[0, 1, 2, 3, 5, 6, 7]);
+    let count = if type_id >= 5 {
+        2
+    } else {
+        rng.range(1, 4.min(budget as i64 - 1)) as usize
+    };
+
+    let mut children = Vec::new();
+    let values = (0..count)
+        .map(|_| bits_packet(rng, &mut children, (budget - 1) / count))
+        .collect::<Vec<_>>();
+
+    let product = values
+        .iter()
+        .try_fold(1u64, |product, value| product.checked_mul(*value))
+        .filter(|product| *product <= MAX_PRODUCT);
+
+    if type_id == 1 && product.is_none() {
+        type_id = 0;
+    }
+
+    push_bits(bits, type_id, 3);
+
+    if children.len() < 1 << 15 && rng.ratio(1, 2) {
+        push_bits(bits, 0, 1);
+        push_bits(bits, children.len() as u64, 15);
+    } else {
+        push_bits(bits, 1, 1);
+        push_bits(bits, count as u64, 11);
+    }
+
+    bits.extend(children);
+
+    match type_id {
+        0 => values.iter().sum(),
+        1 => product.unwrap(),
+        2 => *values.iter().min().unwrap(),
+        3 => *values.iter().max().unwrap(),
+        5 => (values[0] > values[1]) as u64,
+        6 => (values[0] < values[1]) as u64,
+        _ => (values[0] == values[1]) as u64,
+    }
+}
+
+/// A transmission of about `size` packets.
+pub fn day16(rng: &mut Rng, size: usize) -> String {
+    let mut bits = Vec::new();
+
+    bits_packet(rng, &mut bits, size);
+
+    bits.chunks(4)
+        .map(|nibble| {
+            let value = (0..4).fold(0, |value, i| {
+                value * 2 + nibble.get(i).copied().unwrap_or(false) as u32
+            });
+
+            char::from_digit(value, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect()
+}
+
+/// A target area about `size` units deep, reachable by a probe whose horizontal
+/// speed drops to zero above it.
+pub fn day17(rng: &mut Rng, size: usize) -> String {
+    let x_velocity = rng.range(6, 6 + size as i64 / 5);
+    let x_stop = x_velocity * (x_velocity + 1) / 2;
+    let x_min = x_stop - rng.range(0, x_velocity - 1);
+    let x_max = x_stop + rng.range(0, x_velocity * 2);
+    let y_min = -rng.range(10, 10 + size as i64);
+    let y_max = y_min + rng.range(0, -y_min - 1);
+
+    format!(
+        "target area: x={}..{}, y={}..{}",
+        x_min, x_max, y_min, y_max
+    )
+}
+
+fn snailfish_number(rng: &mut Rng, depth: usize) -> String {
+    let element = |rng: &mut Rng| {
+        if depth == 4 || rng.ratio(2, 5) {
+            rng.below(10).to_string()
+        } else {
+            snailfish_number(rng, depth + 1)
+        }
+    };
+
+    let left = element(rng);
+    let right = element(rng);
+
+    format!("[{},{}]", left, right)
+}
+
+/// `size` reduced snailfish numbers.
+pub fn day18(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(2)).map(|_| snailfish_number(rng, 1)))
+}
+
+/// Scanner reports along with the answers they should produce.
+#[derive(Clone, Debug)]
+pub struct ScannerReports {
+    pub input: String,
+    pub scanners: Vec<Vec3<i32>>,
+    pub beacons: usize,
+    pub largest_distance: i32,
+}
+
+/// `size` scanners, each about 1100 units away from one of the previous three and sharing
+/// at least 12 beacons with it. The solution slows down quickly beyond a dozen scanners.
+pub fn day19_reports(rng: &mut Rng, size: usize) -> ScannerReports {
+    const RANGE: i64 = 1000;
+    const SPACING: (i64, i64) = (1000, 1250);
+    const DRIFT: i64 = 250;
+
+    let size = size.max(2);
+    let mut scanners = vec![Vec3::new(0, 0, 0)];
+    let mut beacons = HashSet::new();
+
+    let random_beacon = |rng: &mut Rng, around: &[Vec3<i32>]| {
+        let axis = |rng: &mut Rng, coordinate: fn(&Vec3<i32>) -> i32| {
+            let min = around.iter().map(|s| coordinate(s) as i64).max().unwrap() - RANGE;
+            let max = around.iter().map(|s| coordinate(s) as i64).min().unwrap() + RANGE;
+
+            rng.range(min, max) as i32
+        };
+
+        Vec3::new(axis(rng, |v| v.x), axis(rng, |v| v.y), axis(rng, |v| v.z))
+    };
+
+    for _ in 0..rng.range(8, 12) {
+        beacons.insert(random_beacon(rng, &scanners));
+    }
+
+    while scanners.len() < size {
+        let parent = scanners[scanners.len() - 1 - rng.below(scanners.len().min(3))];
+        let mut offset = [0; 3].map(|_| rng.range(-DRIFT, DRIFT) as i32);
+        offset[rng.below(3)] = (rng.range(SPACING.0, SPACING.1) * rng.choose(&[-1, 1])) as i32;
+        let scanner = parent + Vec3::new(offset[0], offset[1], offset[2]);
+
+        for _ in 0..12 {
+            beacons.insert(random_beacon(rng, &[parent, scanner]));
+        }
+
+        for _ in 0..rng.range(0, 4) {
+            beacons.insert(random_beacon(rng, &[scanner]));
+        }
+
+        scanners.push(scanner);
+    }
+
+    let reports = scanners.iter().enumerate().map(|(i, scanner)| {
+        let rotation = rng.below(24);
+        let mut report = format!("--- scanner {} ---", i);
+
+        for beacon in beacons.iter() {
+            if beacon.chebyshev(*scanner) <= RANGE as i32 {
+                let seen = (*beacon - *scanner).rotations()[rotation];
+                write!(report, "\n{},{},{}", seen.x, seen.y, seen.z).unwrap();
+            }
+        }
+
+        report
+    });
+    let input = reports.collect::<Vec<_>>().join("\n\n");
+
+    let largest_distance = scanners
+        .iter()
+        .flat_map(|a| scanners.iter().map(move |b| a.manhattan(*b)))
+        .max()
+        .unwrap();
+
+    ScannerReports {
+        input,
+        beacons: beacons.len(),
+        scanners,
+        largest_distance,
+    }
+}
+
+/// `size` scanners, see [`day19_reports`].
+pub fn day19(rng: &mut Rng, size: usize) -> String {
+    day19_reports(rng, size).input
+}
+
+/// A random enhancement algorithm and a `size` x `size` image. The algorithm never
+/// lights the infinite background on two consecutive steps.
+pub fn day20(rng: &mut Rng, size: usize) -> String {
+    let pixel = |rng: &mut Rng| if rng.ratio(1, 2) { '#' } else { '.' };
+
+    let mut algorithm = (0..512).map(|_| pixel(rng)).collect::<Vec<_>>();
+
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+
+    let image = lines((0..size).map(|_| (0..size).map(|_| pixel(rng)).collect()));
+
+    format!("{}\n\n{}", algorithm.into_iter().collect::<String>(), image)
+}
+
+/// Two random starting positions; `size` is ignored.
+pub fn day21(rng: &mut Rng, _size: usize) -> String {
+    format!(
+        "Player 1 starting position: {}\nPlayer 2 starting position: {}",
+        rng.range(1, 10),
+        rng.range(1, 10)
+    )
+}
+
+/// `size` reboot steps, the first 20 of them inside the initialization region.
+pub fn day22(rng: &mut Rng, size: usize) -> String {
+    const INIT_STEPS: usize = 20;
+
+    lines((0..size).map(|i| {
+        let (extent, length) = if i < INIT_STEPS {
+            (50, (5, 40))
+        } else {
+            (100_000, (5_000, 40_000))
+        };
+
+        let range = |rng: &mut Rng, axis: char| {
+            let min = rng.range(-extent, extent);
+            let max = (min + rng.range(length.0, length.1)).min(extent);
+
+            format!("{}={}..{}", axis, min, max)
+        };
+
+        let state = if i == 0 || rng.ratio(3, 4) {
+            "on"
+        } else {
+            "off"
+        };
+
+        format!(
+            "{} {},{},{}",
+            state,
+            range(rng, 'x'),
+            range(rng, 'y'),
+            range(rng, 'z')
+        )
+    }))
+}
+
+/// A random burrow with two amphipods of each type; `size` is ignored.
+pub fn day23(rng: &mut Rng, _size: usize) -> String {
+    let mut amphipods = ['A', 'A', 'B', 'B', 'C', 'C', 'D', 'D'];
+    rng.shuffle(&mut amphipods);
+
+    let row = |row: &[char]| {
+        row.iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>()
+            .join("#")
+    };
+
+    format!(
+        "#############\n#...........#\n###{}###\n  #{}#\n  #########",
+        row(&amphipods[..4]),
+        row(&amphipods[4..])
+    )
+}
+
+/// A MONAD program with at least one valid model number; `size` is ignored.
+pub fn day24(rng: &mut Rng, _size: usize) -> String {
+    const DIGITS: usize = 14;
+
+    let mut opened = 0;
+    let mut stack = Vec::new();
+    let mut push_add_y = [0; DIGITS];
+    let mut blocks = Vec::with_capacity(DIGITS);
+
+    for i in 0..DIGITS {
+        let remaining = DIGITS - i;
+
+        if opened < DIGITS / 2 && (stack.is_empty() || (stack.len() < remaining && rng.ratio(1, 2)))
+        {
+            push_add_y[i] = rng.range(1, 16);
+            stack.push(i);
+            opened += 1;
+            blocks.push((1, rng.range(10, 15), push_add_y[i]));
+        } else {
+            let push = stack.pop().unwrap();
+            let difference = rng.range(-8, 8);
+
+            blocks.push((26, difference - push_add_y[push], rng.range(1, 16)));
+        }
+    }
+
+    lines(blocks.into_iter().map(|(div_z, add_x, add_y)| {
+        format!(
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {}\nadd x {}\neql x w\neql x 0\n\
+             mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {}\n\
+             mul y x\nadd z y",
+            div_z, add_x, add_y
+        )
+    }))
+}
+
+/// A `size` x `size` sea floor that stops moving within `20 * size` steps. Random maps
+/// of that density often keep moving forever, so after a number of attempts the share
+/// of empty tiles is lowered, down to a full sea floor on which nothing ever moves.
+pub fn day25(rng: &mut Rng, size: usize) -> String {
+    const ATTEMPTS: usize = 10;
+
+    for empty in (0..=9).rev() {
+        for _ in 0..ATTEMPTS {
+            let input = lines((0..size).map(|_| {
+                (0..size)
+                    .map(|_| match rng.below(20) {
+                        n if n < empty => '.',
+                        n if n < empty + (20 - empty) / 2 => '>',
+                        _ => 'v',
+                    })
+                    .collect()
+            }));
+
+            if Day25::parse(&input)
+                .unwrap()
+                .settles_within(20 * size)
+                .is_some()
+            {
+                return input;
+            }
+        }
+    }
+
+    unreachable!("a full sea floor never moves")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{find, solutions};
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(16, 7, Some(20)), generate(16, 7, Some(20)));
+        assert_ne!(generate(16, 7, Some(20)), generate(16, 8, Some(20)));
+        assert!(generate(26, 7, None).is_none());
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for day in 1..=25 {
+            // Paths through caves and scanner alignments grow too quickly for debug builds;
+            // larger scanner reports are checked against their ground truth below.
+            let large = match day {
+                12 => 8,
+                19 => 4,
+                _ => 20,
+            };
+
+            for (seed, size) in [(0, 4), (1, large)] {
+                let input = generate(day, seed, Some(size)).unwrap();
+
+                for solution in solutions().filter(|s| s.day == day && s.variant.is_none()) {
+                    let output = solution.run(&input);
+
+                    assert!(
+                        output.is_ok(),
+                        "{}, seed {}, size {}: {:?}",
+                        solution,
+                        seed,
+                        size,
+                        output
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn octopuses_synchronize() {
+        for size in [10, 60, 100] {
+            let input = generate(11, 0, Some(size)).unwrap();
+            let mut map = Day11::parse(&input).unwrap();
+
+            assert!((0..1000).any(|_| map.flashes_after_step() == size * size));
+        }
+    }
+
+    #[test]
+    fn scanner_reports_ground_truth() {
+        let reports = day19_reports(&mut Rng::new(19), 3);
+
+        assert_eq!(
+            find(19, 1, None)
+                .unwrap()
+                .run(&reports.input)
+                .unwrap()
+                .answer,
+            reports.beacons.to_string()
+        );
+        assert_eq!(
+            find(19, 2, None)
+                .unwrap()
+                .run(&reports.input)
+                .unwrap()
+                .answer,
+            reports.largest_distance.to_string()
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::geometry::{Vec2, Vec3};
+use std::fmt::{Display, Formatter};
+
+/// A JSON value, written compactly by its `Display` implementation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    /// Kept apart from `Number` so that unsigned values above `i64::MAX` stay exact.
+    Unsigned(u128),
+    /// Written as `null` if not finite.
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Builds an object from its fields, keeping their order.
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, Json)>) -> Self {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        )
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($variant:ident: $($t:ty),*) => {
+        $(
+            impl From<$t> for Json {
+                fn from(value: $t) -> Self {
+                    Json::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(Number: i8, i16, i32, i64);
+impl_from_number!(Unsigned: u8, u16, u32, u64, u128);
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Json::Unsigned(value as u128)
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Float(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Json>> From<Vec2<T>> for Json {
+    fn from(point: Vec2<T>) -> Self {
+        Json::Array(vec![point.x.into(), point.y.into()])
+    }
+}
+
+impl<T: Into<Json>> From<Vec3<T>> for Json {
+    fn from(point: Vec3<T>) -> Self {
+        Json::Array(vec![point.x.into(), point.y.into(), point.z.into()])
+    }
+}
+
+fn write_string(f: &mut Formatter<'_>, string: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+
+    write!(f, "\"")
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(value) => write!(f, "{}", value),
+            Json::Unsigned(value) => write!(f, "{}", value),
+            Json::Float(value) if value.is_finite() => write!(f, "{}", value),
+            Json::Float(_) => write!(f, "null"),
+            Json::String(value) => write_string(f, value),
+            Json::Array(values) => {
+                write!(f, "[")?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, "{}", value)?;
+                }
+
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let json = Json::object([
+            ("answer", "\n#.\"\\".into()),
+            ("path", vec![Vec2::new(0, 1)].into()),
+            ("variant", None::<&str>.into()),
+            ("ok", true.into()),
+            ("ratio", vec![0.5, 2.0, f64::NAN].into()),
+        ]);
+
+        assert_eq!(
+            json.to_string(),
+            r#"{"answer":"\n#.\"\\","path":[[0,1]],"variant":null,"ok":true,"ratio":[0.5,2,null]}"#
+        );
+    }
+
+    #[test]
+    fn large_numbers() {
+        let json = Json::from(vec![
+            Json::from(u64::MAX),
+            usize::MAX.into(),
+            u128::MAX.into(),
+            i64::MIN.into(),
+        ]);
+
+        assert_eq!(
+            json.to_string(),
+            format!("[{},{},{},{}]", u64::MAX, usize::MAX, u128::MAX, i64::MIN)
+        );
+    }
+}
@@ -1,3 +1,715 @@
-use aoc_runner_derive::aoc_main;
+use advent_of_code_2021::answers::{self, AnswerStore, Check};
+use advent_of_code_2021::day01;
+use advent_of_code_2021::day02::{self, Day02, SurfacePolicy};
+use advent_of_code_2021::day03::{
+    BitCriteria, BitOrder, BitPolicy, Criteria, Day03, TieBreak, Trie,
+};
+use advent_of_code_2021::frames;
+use advent_of_code_2021::json::Json;
+use advent_of_code_2021::runner::{self, Output, Solution};
+use advent_of_code_2021::{inputs, solver, RunError, Solver};
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
 
-aoc_main! { lib = advent_of_code_2021 }
+const USAGE: &str = "Usage:
+    aoc2021 run --day <DAY> --part <PART> [--variant <NAME>] [--input <PATH|->] [--json]
+    aoc2021 verify [--inputs <DIR>] [--answers <PATH>] [--record]
+    aoc2021 generate --day <DAY> [--seed <SEED>] [--size <SIZE>]
+    aoc2021 frames --day <11|20|25> [--input <PATH|->] [--steps <N>]
+                   [--format ascii|pgm|ppm|gif] [--output <PATH>] [--scale <N>]
+    aoc2021 all [--inputs <DIR> | --generated [--seed <SEED>]] [--threads <N>] [--json]
+    aoc2021 sonar [--input <PATH|->] [--window <N>]
+    aoc2021 dive [--model simple|aimed|inertial] [--surface error|clamp] [--input <PATH|->]
+                 [--trajectory csv|svg] [--output <PATH>]
+    aoc2021 diagnostic [--input <PATH|->] [--rate] [--policy <POLICY>]
+                       [--columns <COLUMN>=<POLICY>,...] [--order msb|lsb]
+
+`run` reads the puzzle input from PATH, or from stdin if PATH is `-` or omitted.
+
+With `--json`, `run` and `all` print a JSON report instead: answers, timings in
+nanoseconds and day-specific details such as the depth statistics of day 1 or the
+path found on day 15.
+
+`verify` runs every part and variant of a day on each `day<N>*.txt` file in DIR
+(default: input/2021) and compares the answers with those stored in PATH (default:
+answers.txt). With `--record`, answers missing from the store are taken from the
+main implementation of each part and written back to PATH.
+
+`generate` prints a random well-formed input for a day. The same seed (default: 0)
+always gives the same input; the meaning of SIZE depends on the day.
+
+`frames` records each step of the simulation of a day. ASCII frames are printed, PGM
+and PPM frames are written as `step-<N>` files into the directory PATH (default:
+frames), and a GIF animation is written to PATH (default: frames.gif). Each cell is
+SCALE pixels wide (default: 4).
+
+`all` runs every part and variant of every day on the `day<N>*.txt` files of DIR, or
+on generated inputs of the default size, on N threads (default: all available), and
+prints the answers and timings.
+
+`sonar` streams depths from PATH, or from stdin if PATH is `-` or omitted, and counts
+how often the sum of a window of N depths (default: 1) increases, decreases or stays
+the same.
+
+`dive` follows the day 2 commands read from PATH, or from stdin if PATH is `-` or
+omitted, with the given submarine model (default: aimed) and prints where it ends up.
+A command that takes the submarine above the surface is an error, unless the surface
+policy is `clamp`, which stops the submarine at the surface instead. With
+`--trajectory`, the position after every command is written as CSV or as an SVG depth
+profile to PATH, or printed if PATH is omitted.
+
+`diagnostic` finds a rating of the day 3 report read from PATH, or from stdin if PATH
+is `-` or omitted, by keeping the entries whose bits meet a policy, looking at the bits
+in the given order (default: msb, most significant first). With `--rate`, it takes the
+bit chosen by the policy in each column instead. A POLICY is `most` or `least` common,
+followed by the tie-break: `most:one`, `least:zero`, `most:error`... The default
+policy (default: most:one) can be overridden for columns numbered from 0 for the most
+significant bit.";
+
+const DEFAULT_INPUTS: &str = "input/2021";
+const DEFAULT_ANSWERS: &str = "answers.txt";
+
+struct Options(HashMap<String, String>);
+
+impl Options {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = HashMap::new();
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            let key = arg
+                .strip_prefix("--")
+                .ok_or_else(|| format!("unexpected argument `{}`", arg))?;
+            let value = args
+                .next_if(|value| !value.starts_with("--") || value == "-")
+                .unwrap_or_default();
+
+            options.insert(key.to_owned(), value);
+        }
+
+        Ok(Options(options))
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(|value| value.as_str())
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    fn number<T: FromStr>(&self, key: &str) -> Result<T, String> {
+        self.optional_number(key)?
+            .ok_or_else(|| format!("missing required option `--{}`", key))
+    }
+
+    fn optional_number<T: FromStr>(&self, key: &str) -> Result<Option<T>, String> {
+        self.get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value `{}` for `--{}`", value, key))
+            })
+            .transpose()
+    }
+}
+
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        None | Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => fs::read_to_string(path),
+    }
+}
+
+fn sonar(options: &Options) -> Result<(), Box<dyn Error>> {
+    let window = options
+        .optional_number("window")?
+        .unwrap_or(day01::SonarAnalyzer::DEPTHS);
+
+    if window == 0 {
+        return Err("the window must hold at least one depth".into());
+    }
+
+    let reader: Box<dyn BufRead> = match options.get("input") {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(io::BufReader::new(fs::File::open(path)?)),
+    };
+    let counts = day01::analyze_reader(reader, window)?;
+
+    println!("increases: {}", counts.increases);
+    println!("decreases: {}", counts.decreases);
+    println!("plateaus: {}", counts.plateaus);
+
+    Ok(())
+}
+
+fn dive(options: &Options) -> Result<(), Box<dyn Error>> {
+    let name = options.get("model").unwrap_or("aimed");
+    let policy = match options.get("surface").unwrap_or("error") {
+        "error" => SurfacePolicy::Error,
+        "clamp" => SurfacePolicy::Clamp,
+        policy => return Err(format!("unknown surface policy `{}`", policy).into()),
+    };
+    let mut model = day02::model(name, policy).ok_or_else(|| {
+        format!(
+            "unknown model `{}`, expected one of {}",
+            name,
+            day02::MODELS.join(", ")
+        )
+    })?;
+    let commands = Day02::parse(&read_input(options.get("input"))?)?;
+
+    if let Some(format) = options.get("trajectory") {
+        let trajectory = day02::record(model.as_mut(), &commands)?;
+        let mut out: Box<dyn io::Write> = match options.get("output") {
+            Some(path) => Box::new(io::BufWriter::new(fs::File::create(path)?)),
+            None => Box::new(io::stdout().lock()),
+        };
+
+        match format {
+            "csv" => trajectory.write_csv(&mut out)?,
+            "svg" => trajectory.write_svg(&mut out)?,
+            format => return Err(format!("unknown trajectory format `{}`", format).into()),
+        }
+
+        return Ok(out.flush()?);
+    }
+
+    let product = day02::navigate(model.as_mut(), &commands)?;
+
+    println!("horizontal position: {}", model.horizontal_position());
+    println!("depth: {}", model.depth());
+    println!("product: {}", product);
+
+    Ok(())
+}
+
+fn bit_policy(policy: &str) -> Result<BitPolicy, String> {
+    let (criteria, tie_break) = policy.split_once(':').unwrap_or((policy, ""));
+
+    let criteria = match criteria {
+        "most" => BitCriteria::MostCommon,
+        "least" => BitCriteria::LeastCommon,
+        _ => return Err(format!("unknown bit criteria in policy `{}`", policy)),
+    };
+    let tie_break = match tie_break {
+        "one" => TieBreak::PreferOne,
+        "zero" => TieBreak::PreferZero,
+        "error" => TieBreak::Error,
+        _ => return Err(format!("unknown tie-break in policy `{}`", policy)),
+    };
+
+    Ok(BitPolicy::new(criteria, tie_break))
+}
+
+fn diagnostic(options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut criteria = Criteria::new(bit_policy(options.get("policy").unwrap_or("most:one"))?);
+
+    for column in options.get("columns").unwrap_or("").split(',') {
+        if column.is_empty() {
+            continue;
+        }
+
+        let (index, policy) = column
+            .split_once('=')
+            .ok_or_else(|| format!("expected <COLUMN>=<POLICY>, got `{}`", column))?;
+        let index = index
+            .parse()
+            .map_err(|_| format!("invalid column `{}`", index))?;
+
+        criteria = criteria.with_column(index, bit_policy(policy)?);
+    }
+
+    let order = match options.get("order").unwrap_or("msb") {
+        "msb" => BitOrder::MostSignificantFirst,
+        "lsb" => BitOrder::LeastSignificantFirst,
+        order => return Err(format!("unknown bit order `{}`", order).into()),
+    };
+    let report = Day03::parse(&read_input(options.get("input"))?)?;
+
+    let value = if options.flag("rate") {
+        report.rate(&criteria)?
+    } else {
+        Trie::new(&report, order).rating(&criteria)?
+    };
+
+    println!("{} ({})", value, value.to_decimal());
+
+    Ok(())
+}
+
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let day = options.number("day")?;
+    let part = options.number("part")?;
+    let variant = options.get("variant");
+
+    let solution = runner::find(day, part, variant)?;
+    let input = read_input(options.get("input"))?;
+
+    if options.flag("json") {
+        let result = solution.run(&input);
+        let details = solver(day).map(|solver| solver.details(&input));
+
+        println!(
+            "{}",
+            with_details(runner::json_report(&solution, &result), details)
+        );
+
+        return result.map(|_| ()).map_err(|e| e.into());
+    }
+
+    let output = solution.run(&input)?;
+
+    println!("{} : {}", solution, output.answer);
+    println!("\tgenerator: {:?},", output.parse_time);
+    println!("\trunner: {:?}", output.solve_time);
+
+    Ok(())
+}
+
+/// Input files named after their day, such as `day7.txt` or `day07-large.txt`. Files
+/// named after a day without a solver, such as `day26.txt`, are skipped.
+fn input_files(dir: &Path) -> io::Result<Vec<(u8, PathBuf)>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+
+        let day = name
+            .strip_prefix("day")
+            .filter(|_| name.ends_with(".txt"))
+            .map(|rest| {
+                rest.chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect::<String>()
+            })
+            .and_then(|digits| digits.parse().ok())
+            .filter(|day| solver(*day).is_some());
+
+        if let Some(day) = day {
+            files.push((day, path));
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+fn read_answers(path: &str) -> Result<AnswerStore, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(store) => Ok(AnswerStore::try_from(store.as_str())?),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(AnswerStore::default()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn verify(options: &Options) -> Result<(), Box<dyn Error>> {
+    let answers_path = options.get("answers").unwrap_or(DEFAULT_ANSWERS);
+    let inputs = Path::new(options.get("inputs").unwrap_or(DEFAULT_INPUTS));
+    let record = options.flag("record");
+
+    let mut store = read_answers(answers_path)?;
+    let mut failures = 0;
+
+    for (day, path) in input_files(inputs)? {
+        let input = fs::read_to_string(&path)?;
+        let hash = answers::input_hash(&input);
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        for solution in runner::solutions().filter(|s| s.day == day) {
+            if record && solution.variant.is_none() && store.get(day, solution.part, hash).is_none()
+            {
+                if let Ok(output) = solution.run(&input) {
+                    store.insert(day, solution.part, hash, output.answer);
+                }
+            }
+
+            let outcome = match answers::check(&store, &solution, &input) {
+                Check::Match => "ok".to_owned(),
+                Check::Missing => "no stored answer".to_owned(),
+                Check::Mismatch { expected, actual } => {
+                    failures += 1;
+                    format!("MISMATCH: expected {:?}, got {:?}", expected, actual)
+                }
+                Check::Failed(error) => {
+                    failures += 1;
+                    format!("FAILED: {}", error)
+                }
+            };
+
+            println!("{} ({}) : {}", solution, file_name, outcome);
+        }
+    }
+
+    if record {
+        fs::write(answers_path, store.to_string())?;
+    }
+
+    if failures > 0 {
+        return Err(format!("{} solutions disagree with the stored answers", failures).into());
+    }
+
+    Ok(())
+}
+
+fn generate(options: &Options) -> Result<(), Box<dyn Error>> {
+    let day = options.number("day")?;
+    let seed = options.optional_number("seed")?.unwrap_or(0);
+    let size = options.optional_number("size")?;
+
+    let input = inputs::generate(day, seed, size).ok_or_else(|| format!("no day {}", day))?;
+
+    println!("{}", input);
+
+    Ok(())
+}
+
+fn export_frames(options: &Options) -> Result<(), Box<dyn Error>> {
+    let day = options.number("day")?;
+    let steps = match options.optional_number("steps")? {
+        Some(steps) => steps,
+        None => frames::default_steps(day).unwrap_or_default(),
+    };
+    let scale = options.optional_number("scale")?.unwrap_or(4);
+    let input = read_input(options.get("input"))?;
+
+    let frames = frames::simulate(day, &input, steps)
+        .ok_or_else(|| format!("day {} is not a grid simulation", day))??;
+
+    match options.get("format").unwrap_or("ascii") {
+        "ascii" => println!("{}", frames.to_ascii()),
+        format @ ("pgm" | "ppm") => {
+            let dir = Path::new(options.get("output").unwrap_or("frames"));
+            fs::create_dir_all(dir)?;
+
+            for step in 0..frames.len() {
+                let path = dir.join(format!("step-{:04}.{}", step, format));
+                let mut file = io::BufWriter::new(fs::File::create(path)?);
+
+                match format {
+                    "pgm" => frames.write_pgm(step, scale, &mut file)?,
+                    _ => frames.write_ppm(step, scale, &mut file)?,
+                }
+            }
+        }
+        "gif" => {
+            let path = options.get("output").unwrap_or("frames.gif");
+            let mut file = io::BufWriter::new(fs::File::create(path)?);
+
+            frames.write_gif(scale, 10, &mut file)?;
+        }
+        format => return Err(format!("unknown frame format `{}`", format).into()),
+    }
+
+    Ok(())
+}
+
+fn format_answer(result: &Result<Output, RunError>) -> String {
+    match result {
+        Ok(output) if output.answer.contains('\n') => {
+            format!("({} lines)", output.answer.trim().lines().count())
+        }
+        Ok(output) => output.answer.to_owned(),
+        Err(error) => format!("error: {}", error),
+    }
+}
+
+enum Job {
+    Solve(Solution, usize),
+    Details(u8, usize),
+}
+
+enum JobResult {
+    Solved(Result<Output, RunError>),
+    Details(Result<Json, RunError>),
+}
+
+fn with_details(report: Json, details: Option<Result<Json, RunError>>) -> Json {
+    match (report, details) {
+        (Json::Object(mut fields), Some(details)) => {
+            let details = details.unwrap_or_else(|error| error.to_string().into());
+
+            fields.push(("details".to_owned(), details));
+            Json::Object(fields)
+        }
+        (report, _) => report,
+    }
+}
+
+fn run_all(options: &Options) -> Result<(), Box<dyn Error>> {
+    let json = options.flag("json");
+    let threads = match options.optional_number("threads")? {
+        Some(threads) => threads,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+
+    let inputs = if options.flag("generated") {
+        let seed = options.optional_number("seed")?.unwrap_or(0);
+
+        (1..=25)
+            .map(|day| {
+                let input = inputs::generate(day, seed, None).unwrap_or_default();
+                (day, format!("seed {}", seed), input)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        let dir = Path::new(options.get("inputs").unwrap_or(DEFAULT_INPUTS));
+
+        input_files(dir)?
+            .into_iter()
+            .map(|(day, path)| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                Ok((day, name.into_owned(), fs::read_to_string(&path)?))
+            })
+            .collect::<io::Result<Vec<_>>>()?
+    };
+
+    let mut jobs = Vec::new();
+
+    for (index, (day, _, _)) in inputs.iter().enumerate() {
+        if json {
+            jobs.push(Job::Details(*day, index));
+        }
+
+        jobs.extend(
+            runner::solutions()
+                .filter(|s| s.day == *day)
+                .map(|s| Job::Solve(s, index)),
+        );
+    }
+
+    let start = Instant::now();
+    let results = runner::run_parallel(&jobs, threads, |job| match job {
+        Job::Solve(solution, index) => JobResult::Solved(solution.run(&inputs[*index].2)),
+        Job::Details(day, index) => {
+            JobResult::Details(solver(*day).unwrap().details(&inputs[*index].2))
+        }
+    })
+    .into_iter()
+    .zip(jobs.iter())
+    .map(|(result, job)| {
+        result.unwrap_or_else(|error| match job {
+            Job::Solve(..) => JobResult::Solved(Err(error)),
+            Job::Details(..) => JobResult::Details(Err(error)),
+        })
+    })
+    .collect::<Vec<_>>();
+    let wall_clock = start.elapsed();
+
+    let solved = jobs
+        .iter()
+        .zip(results.iter())
+        .filter_map(|(job, result)| match (job, result) {
+            (Job::Solve(solution, index), JobResult::Solved(result)) => {
+                Some((solution, *index, result))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let parse_total = solved
+        .iter()
+        .filter_map(|(_, _, result)| result.as_ref().ok())
+        .map(|output| output.parse_time)
+        .sum::<Duration>();
+    let solve_total = solved
+        .iter()
+        .filter_map(|(_, _, result)| result.as_ref().ok())
+        .map(|output| output.solve_time)
+        .sum::<Duration>();
+
+    if json {
+        let mut details = results.iter().filter_map(|result| match result {
+            JobResult::Details(details) => Some(details.clone()),
+            _ => None,
+        });
+
+        let inputs_json = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, (day, name, _))| {
+                let solutions = solved
+                    .iter()
+                    .filter(|(_, i, _)| *i == index)
+                    .map(|(solution, _, result)| runner::json_report(solution, result))
+                    .collect::<Vec<_>>();
+
+                Json::object([
+                    ("day", (*day).into()),
+                    ("input", name.as_str().into()),
+                    ("solutions", solutions.into()),
+                ])
+            })
+            .map(|input| with_details(input, details.next()))
+            .collect::<Vec<_>>();
+
+        println!(
+            "{}",
+            Json::object([
+                ("threads", threads.into()),
+                ("wall_clock_ns", wall_clock.as_nanos().into()),
+                ("parse_time_ns", parse_total.as_nanos().into()),
+                ("solve_time_ns", solve_total.as_nanos().into()),
+                ("inputs", inputs_json.into()),
+            ])
+        );
+
+        return Ok(());
+    }
+
+    let mut rows = vec![[
+        "Day".to_owned(),
+        "Part".to_owned(),
+        "Variant".to_owned(),
+        "Input".to_owned(),
+        "Answer".to_owned(),
+        "Parse".to_owned(),
+        "Solve".to_owned(),
+    ]];
+
+    for (solution, index, result) in solved.iter() {
+        let (parse_time, solve_time) = match result {
+            Ok(output) => (
+                format!("{:.2?}", output.parse_time),
+                format!("{:.2?}", output.solve_time),
+            ),
+            Err(_) => ("-".to_owned(), "-".to_owned()),
+        };
+
+        rows.push([
+            solution.day.to_string(),
+            solution.part.to_string(),
+            solution.variant.unwrap_or("-").to_owned(),
+            inputs[*index].1.to_owned(),
+            format_answer(result),
+            parse_time,
+            solve_time,
+        ]);
+    }
+
+    let mut widths = [0; 7];
+
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in rows.iter() {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        println!("{}", line.trim_end());
+    }
+
+    let missing = (1..=25)
+        .filter(|day| inputs.iter().all(|(d, _, _)| d != day))
+        .map(|day| day.to_string())
+        .collect::<Vec<_>>();
+
+    if !missing.is_empty() {
+        println!("\nNo input for days {}", missing.join(", "));
+    }
+
+    println!(
+        "\n{} solutions on {} threads: {:.2?} wall clock, {:.2?} parsing and {:.2?} solving",
+        solved.len(),
+        threads,
+        wall_clock,
+        parse_total,
+        solve_total
+    );
+
+    Ok(())
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+
+    let result = match args.next().as_deref() {
+        Some("run") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| run(&o)),
+        Some("verify") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| verify(&o)),
+        Some("all") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| run_all(&o)),
+        Some("frames") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| export_frames(&o)),
+        Some("generate") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| generate(&o)),
+        Some("diagnostic") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| diagnostic(&o)),
+        Some("dive") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| dive(&o)),
+        Some("sonar") => Options::parse(args)
+            .map_err(|e| e.into())
+            .and_then(|o| sonar(&o)),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_files_skip_unknown_days() {
+        let dir = env::temp_dir().join(format!("aoc2021-inputs-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        for name in [
+            "day01.txt",
+            "day07-large.txt",
+            "day0.txt",
+            "day26.txt",
+            "day300.txt",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let days = input_files(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|(day, _)| day)
+            .collect::<Vec<_>>();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(days, vec![1, 7]);
+    }
+}
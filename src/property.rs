@@ -0,0 +1,102 @@
+//! A small property-testing harness: random cases from a seeded generator, and greedy
+//! shrinking of a failing case to a minimal counterexample.
+
+use crate::inputs::Rng;
+use std::fmt::Debug;
+
+const CASES: usize = 200;
+
+/// Checks `property` on random values drawn by `generate`. A failing value is shrunk by
+/// repeatedly replacing it with the first of its `shrink` candidates that still fails.
+pub fn check<T: Debug>(
+    seed: u64,
+    mut generate: impl FnMut(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) {
+    let mut rng = Rng::new(seed);
+
+    for case in 0..CASES {
+        let mut value = generate(&mut rng);
+
+        if property(&value) {
+            continue;
+        }
+
+        while let Some(smaller) = shrink(&value).into_iter().find(|v| !property(v)) {
+            value = smaller;
+        }
+
+        panic!(
+            "property failed on case {} of seed {}, minimal counterexample: {:?}",
+            case, seed, value
+        );
+    }
+}
+
+/// Shorter vectors of at least `min_len` elements, then vectors with one element shrunk.
+pub fn shrink_vec<T: Clone>(
+    values: &[T],
+    min_len: usize,
+    shrink: impl Fn(&T) -> Vec<T>,
+) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+
+    if values.len() > min_len {
+        candidates.push(values[..min_len.max(values.len() / 2)].to_vec());
+
+        for i in 0..values.len() {
+            let mut shorter = values.to_vec();
+            shorter.remove(i);
+            candidates.push(shorter);
+        }
+    }
+
+    for (i, value) in values.iter().enumerate() {
+        for smaller in shrink(value) {
+            let mut candidate = values.to_vec();
+            candidate[i] = smaller;
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
+
+/// Integers closer to zero.
+pub fn shrink_int(value: &i32) -> Vec<i32> {
+    let mut candidates = vec![0, value / 2, value - value.signum()];
+    candidates.dedup();
+    candidates.retain(|candidate| candidate != value);
+    candidates
+}
+
+/// No smaller values.
+pub fn no_shrink<T>(_: &T) -> Vec<T> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "minimal counterexample: [10]")]
+    fn shrinks_to_minimal_counterexample() {
+        check(
+            0,
+            |rng| {
+                (0..20)
+                    .map(|_| rng.range(0, 100) as i32)
+                    .collect::<Vec<_>>()
+            },
+            |values| shrink_vec(values, 0, shrink_int),
+            |values| values.iter().all(|value| *value < 10),
+        );
+    }
+
+    #[test]
+    fn passing_property() {
+        check(0, |rng| rng.range(0, 10), no_shrink, |value| *value <= 10);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::error::{ParseError, RunError};
+use crate::json::Json;
+use crate::solver::{solvers, DynSolver};
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+pub struct Output {
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// A runnable part of a day, optionally one of its named alternative implementations.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub variant: Option<&'static str>,
+    solver: &'static dyn DynSolver,
+}
+
+impl Solution {
+    /// Parses `input` and solves the puzzle, timing both stages separately. A panic is
+    /// reported as [`RunError::Panicked`].
+    pub fn run(&self, input: &str) -> Result<Output, RunError> {
+        catch_panic(|| {
+            self.solver
+                .run(self.part, self.variant, input.trim_end_matches('\n'))
+        })?
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Day {} - Part {}", self.day, self.part)?;
+
+        if let Some(variant) = self.variant {
+            write!(f, " - {}", variant)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn solutions() -> impl Iterator<Item = Solution> {
+    solvers().flat_map(|solver| {
+        (1..=solver.parts()).flat_map(move |part| {
+            [None]
+                .into_iter()
+                .chain(solver.variants(part).into_iter().map(Some))
+                .map(move |variant| Solution {
+                    day: solver.day(),
+                    part,
+                    variant,
+                    solver,
+                })
+        })
+    })
+}
+
+pub fn find(day: u8, part: u8, variant: Option<&str>) -> Result<Solution, RunError> {
+    solutions()
+        .find(|s| s.day == day && s.part == part && s.variant == variant)
+        .ok_or_else(|| RunError::UnknownSolution {
+            day,
+            part,
+            variant: variant.map(|v| v.to_owned()),
+        })
+}
+
+/// A machine-readable report of running `solution`.
+pub fn json_report(solution: &Solution, result: &Result<Output, RunError>) -> Json {
+    let mut fields = vec![
+        ("day", solution.day.into()),
+        ("part", solution.part.into()),
+        ("variant", solution.variant.into()),
+    ];
+
+    match result {
+        Ok(output) => fields.extend([
+            ("answer", output.answer.as_str().into()),
+            ("parse_time_ns", output.parse_time.as_nanos().into()),
+            ("solve_time_ns", output.solve_time.as_nanos().into()),
+        ]),
+        Err(error) => fields.push(("error", error.to_string().into())),
+    }
+
+    Json::object(fields)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => (*message).to_owned(),
+            Err(_) => "unknown panic".to_owned(),
+        },
+    }
+}
+
+/// Runs `f`, turning a panic into [`RunError::Panicked`].
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, RunError> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|payload| RunError::Panicked(panic_message(payload)))
+}
+
+/// Applies `run` to every job using up to `threads` threads, and returns the results in
+/// the order of `jobs`. A job that panics gets [`RunError::Panicked`] without stopping
+/// the others.
+pub fn run_parallel<J: Sync, R: Send>(
+    jobs: &[J],
+    threads: usize,
+    run: impl Fn(&J) -> R + Sync,
+) -> Vec<Result<R, RunError>> {
+    let next_job = AtomicUsize::new(0);
+    let results = Mutex::new((0..jobs.len()).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next_job.fetch_add(1, Ordering::Relaxed);
+                let job = match jobs.get(index) {
+                    Some(job) => job,
+                    None => break,
+                };
+                let result = catch_panic(|| run(job));
+
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every job is run"))
+        .collect()
+}
+
+/// An answer that can be reported; `None` means the input has no solution.
+pub trait Answer {
+    fn into_answer(self) -> Option<String>;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+
+            impl Answer for Option<$t> {
+                fn into_answer(self) -> Option<String> {
+                    self.map(|answer| answer.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer!(i32, i64, u32, u64, usize, String);
+
+pub(crate) fn timed<I, A: Answer>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<I, ParseError>,
+    solve: impl Fn(&I) -> A,
+) -> Result<Output, RunError> {
+    let start = Instant::now();
+    let parsed = parse(input)?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = solve(&parsed);
+    let solve_time = start.elapsed();
+
+    Ok(Output {
+        answer: answer.into_answer().ok_or(RunError::NoAnswer)?,
+        parse_time,
+        solve_time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_variant() {
+        let solution = find(7, 1, Some("median")).unwrap();
+
+        assert_eq!(
+            solution.run("16,1,2,0,4,2,7,1,2,14\n").unwrap().answer,
+            "37"
+        );
+    }
+
+    #[test]
+    fn parallel_results_keep_job_order() {
+        let input = "16,1,2,0,4,2,7,1,2,14";
+        let jobs = solutions()
+            .filter(|s| s.day == 7)
+            .map(|s| (s, input))
+            .collect::<Vec<_>>();
+
+        let answers = run_parallel(&jobs, 3, |(solution, input)| solution.run(input))
+            .into_iter()
+            .map(|output| output.unwrap().unwrap().answer)
+            .collect::<Vec<_>>();
+
+        assert_eq!(answers, ["37", "37", "168", "168"]);
+    }
+
+    #[test]
+    fn parallel_panics_are_reported() {
+        let jobs = [1, 0, 2];
+
+        let results = run_parallel(&jobs, 2, |n| {
+            assert!(*n > 0, "job {} failed", n);
+            n * 10
+        });
+
+        assert_eq!(
+            results
+                .into_iter()
+                .map(|result| result.map_err(|e| e.to_string()))
+                .collect::<Vec<_>>(),
+            [Ok(10), Err("panicked: job 0 failed".to_owned()), Ok(20)]
+        );
+    }
+
+    #[test]
+    fn empty_input_never_panics() {
+        for solution in solutions() {
+            let result = solution.run("");
+
+            assert!(
+                !matches!(result, Err(RunError::Panicked(_))),
+                "{}: {:?}",
+                solution,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_solution() {
+        assert!(find(25, 2, None).is_err());
+        assert!(find(7, 1, Some("mean")).is_err());
+    }
+}
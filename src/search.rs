@@ -0,0 +1,158 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// A cheapest path found by a search, from the start state to a goal state inclusive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    pub states: Vec<S>,
+}
+
+#[derive(Eq, PartialEq)]
+struct Entry {
+    estimate: usize,
+    cost: usize,
+    id: usize,
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Finds a cheapest path from `start` to any state satisfying `is_goal`.
+/// `neighbors` yields the states reachable in one move along with the cost of the move.
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    a_star(start, neighbors, |_| 0, is_goal)
+}
+
+/// Same as [`dijkstra`], guided by `heuristic`. The heuristic must never overestimate
+/// the remaining cost to a goal, otherwise the path found may not be the cheapest.
+pub fn a_star<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut heap = BinaryHeap::new();
+    let mut ids = HashMap::new();
+    let mut states: Vec<S> = Vec::new();
+    let mut costs = Vec::new();
+    let mut parents: Vec<Option<usize>> = Vec::new();
+
+    heap.push(Entry {
+        estimate: heuristic(&start),
+        cost: 0,
+        id: 0,
+    });
+    ids.insert(start.clone(), 0);
+    states.push(start);
+    costs.push(0);
+    parents.push(None);
+
+    while let Some(Entry { cost, id, .. }) = heap.pop() {
+        if cost > costs[id] {
+            continue;
+        }
+
+        if is_goal(&states[id]) {
+            let mut path = vec![states[id].clone()];
+            let mut current = id;
+
+            while let Some(parent) = parents[current] {
+                path.push(states[parent].clone());
+                current = parent;
+            }
+
+            path.reverse();
+
+            return Some(Path { cost, states: path });
+        }
+
+        for (next, step_cost) in neighbors(&states[id]) {
+            let next_cost = cost + step_cost;
+
+            let next_id = match ids.get(&next) {
+                Some(&next_id) if next_cost >= costs[next_id] => continue,
+                Some(&next_id) => {
+                    costs[next_id] = next_cost;
+                    parents[next_id] = Some(id);
+                    next_id
+                }
+                None => {
+                    let next_id = states.len();
+
+                    ids.insert(next.clone(), next_id);
+                    states.push(next);
+                    costs.push(next_cost);
+                    parents.push(Some(id));
+                    next_id
+                }
+            };
+
+            heap.push(Entry {
+                estimate: next_cost + heuristic(&states[next_id]),
+                cost: next_cost,
+                id: next_id,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(node: &char) -> Vec<(char, usize)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 4)],
+            'b' => vec![('c', 2), ('d', 6)],
+            'c' => vec![('d', 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_reconstructs_path() {
+        let path = dijkstra('a', graph, |node| *node == 'd').unwrap();
+
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states, vec!['a', 'b', 'c', 'd']);
+        assert!(dijkstra('d', graph, |node| *node == 'a').is_none());
+    }
+
+    #[test]
+    fn a_star_matches_dijkstra() {
+        let heuristic = |node: &char| 'd' as usize - *node as usize;
+
+        assert_eq!(
+            a_star('a', graph, heuristic, |node| *node == 'd'),
+            dijkstra('a', graph, |node| *node == 'd')
+        );
+    }
+}
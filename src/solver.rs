@@ -0,0 +1,167 @@
+use crate::error::{ParseError, RunError};
+use crate::json::Json;
+use crate::runner::{timed, Answer, Output};
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+
+pub type Variant<I, A> = (&'static str, fn(&I) -> A);
+
+/// A puzzle of a single day: a parser for its input and a solution for each part.
+pub trait Solver {
+    const DAY: u8;
+
+    /// Number of parts with a puzzle; the last day of the calendar only has one.
+    const PARTS: u8 = 2;
+
+    type Input;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Alternative implementations of part 1, by name.
+    fn part1_variants() -> Vec<Variant<Self::Input, Self::Part1>> {
+        Vec::new()
+    }
+
+    /// Alternative implementations of part 2, by name.
+    fn part2_variants() -> Vec<Variant<Self::Input, Self::Part2>> {
+        Vec::new()
+    }
+
+    /// Day-specific details behind the answers, such as a path or positions, for
+    /// machine-readable output.
+    fn details(_input: &Self::Input) -> Vec<(&'static str, Json)> {
+        Vec::new()
+    }
+}
+
+/// Object-safe view of a [`Solver`], with answers rendered as strings.
+pub trait DynSolver: Sync {
+    fn day(&self) -> u8;
+
+    fn parts(&self) -> u8;
+
+    fn variants(&self, part: u8) -> Vec<&'static str>;
+
+    fn run(&self, part: u8, variant: Option<&str>, input: &str) -> Result<Output, RunError>;
+
+    /// Parses `input` and returns the [`Solver::details`] as a JSON object.
+    fn details(&self, input: &str) -> Result<Json, RunError>;
+}
+
+impl<S: Solver + Sync> DynSolver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+
+    fn variants(&self, part: u8) -> Vec<&'static str> {
+        match part {
+            1 => S::part1_variants()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect(),
+            2 => S::part2_variants()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn run(&self, part: u8, variant: Option<&str>, input: &str) -> Result<Output, RunError> {
+        let unknown = || RunError::UnknownSolution {
+            day: S::DAY,
+            part,
+            variant: variant.map(|v| v.to_owned()),
+        };
+
+        match (part, variant) {
+            (1, None) => timed(input, S::parse, S::part1),
+            (2, None) if S::PARTS >= 2 => timed(input, S::parse, S::part2),
+            (1, Some(name)) => S::part1_variants()
+                .into_iter()
+                .find(|(n, _)| *n == name)
+                .map_or_else(|| Err(unknown()), |(_, f)| timed(input, S::parse, f)),
+            (2, Some(name)) => S::part2_variants()
+                .into_iter()
+                .find(|(n, _)| *n == name)
+                .map_or_else(|| Err(unknown()), |(_, f)| timed(input, S::parse, f)),
+            _ => Err(unknown()),
+        }
+    }
+
+    fn details(&self, input: &str) -> Result<Json, RunError> {
+        let input = S::parse(input.trim_end_matches('\n'))?;
+
+        Ok(Json::object(S::details(&input)))
+    }
+}
+
+static SOLVERS: [&dyn DynSolver; 25] = [
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+/// Looks up the solver of a day of the calendar, numbered from 1.
+pub fn solver(day: u8) -> Option<&'static dyn DynSolver> {
+    SOLVERS.get((day as usize).checked_sub(1)?).copied()
+}
+
+pub fn solvers() -> impl Iterator<Item = &'static dyn DynSolver> {
+    SOLVERS.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_ordered_by_day() {
+        assert!(solvers()
+            .enumerate()
+            .all(|(i, s)| s.day() as usize == i + 1));
+        assert!(solver(0).is_none());
+        assert!(solver(26).is_none());
+    }
+
+    #[test]
+    fn typed_solver() {
+        let input = day07::Day07::parse("16,1,2,0,4,2,7,1,2,14").unwrap();
+
+        assert_eq!(day07::Day07::part2(&input), 168);
+    }
+}
@@ -0,0 +1,78 @@
+//! Preprocessing shared by every input parser.
+
+use std::borrow::Cow;
+
+const BOM: char = '\u{feff}';
+
+/// Strips a byte order mark, converts `\r\n` and `\r` line endings to `\n`, and
+/// removes trailing whitespace from every line as well as trailing empty lines.
+/// Line and column numbers are preserved, so parse errors still point at the
+/// original text. Already normalized input is borrowed.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+
+    let is_normalized = !input.contains('\r')
+        && !input.ends_with(char::is_whitespace)
+        && input
+            .lines()
+            .all(|line| !line.ends_with(char::is_whitespace));
+
+    if is_normalized {
+        return Cow::Borrowed(input);
+    }
+
+    let lines = input
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Cow::Owned(lines.trim_end_matches('\n').to_owned())
+}
+
+/// Splits normalized input into blocks of lines separated by one or more empty lines.
+pub fn paragraphs(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim_matches('\n'))
+        .filter(|paragraph| !paragraph.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::generate;
+    use crate::runner::find;
+
+    #[test]
+    fn normalize_line_endings_and_whitespace() {
+        assert_eq!(normalize("\u{feff}a \r\nb\t\r\rc\r\n\r\n  \n"), "a\nb\n\nc");
+        assert!(matches!(normalize("a\n\nb"), Cow::Borrowed("a\n\nb")));
+    }
+
+    #[test]
+    fn mangled_inputs_give_the_same_answers() {
+        for day in 1..=25 {
+            let input = generate(day, 1, Some(4)).unwrap();
+            let mangled = format!("\u{feff}{} \r\n\r\n", input.replace('\n', " \r\n"));
+            let solution = find(day, 1, None).unwrap();
+
+            assert_eq!(
+                solution.run(&mangled).unwrap().answer,
+                solution.run(&input).unwrap().answer,
+                "day {}",
+                day
+            );
+        }
+    }
+
+    #[test]
+    fn split_paragraphs() {
+        assert_eq!(
+            paragraphs("\na\nb\n\n\n\nc\n").collect::<Vec<_>>(),
+            vec!["a\nb", "c"]
+        );
+    }
+}